@@ -1,8 +1,25 @@
 //! This module provides functions to interact with a list containing authors.
+//!
+//! Every line lists one author as <surname, forename>. Blank lines and everything after a '#' are
+//! ignored. A pseudonym can be assigned to the author its releases are reported for via '=', and
+//! options can be appended via '|':
+//!
+//! ```text
+//! # Horror
+//! King, Stephen | tag=horror
+//! Bachman, Richard = King, Stephen | tag=horror
+//! Follett, Ken | languages=/language/eng,/language/ger | formats=Taschenbuch,Buch
+//! ```
 
-use anyhow::Result;
+use crate::customtypes::{Author, MediaFormat};
+use crate::format;
+use anyhow::{Context, Result};
 
-/// Get the authors from a list. In general, they are listed as <surname, forename>.
+const BOM: char = '\u{feff}';
+
+/// Get the authors from a list. In general, they are listed as <surname, forename>. A byte order
+/// mark and surrounding whitespaces are ignored, duplicates (ignoring case and diacritics) are
+/// reported and skipped.
 ///
 /// # Arguments
 ///
@@ -10,22 +27,41 @@ use anyhow::Result;
 ///
 /// # Return
 ///
-/// Ok(Vec<String>) - If successful, return a list of authors
+/// Ok(Vec<Author>) - If successful, return a list of authors
 /// Err(err) - Some error occured
-pub fn read_authors(filename: &str) -> Result<Vec<String>> {
+pub fn read_authors(filename: &str) -> Result<Vec<Author>> {
     log::trace!("authors::read_authors()");
 
-    // first of all, check whether file exists or not
-    if !std::path::Path::new(filename).exists() {
-        anyhow::bail!("File '{}' not found!", filename);
-    }
+    let contents = read_file(filename)?;
+
+    // now parse the file line by line
+    let mut authors: Vec<Author> = Vec::new();
+    let mut known_names = std::collections::HashMap::new();
 
-    // now read the file line by line
-    let mut authors = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line_number = index + 1;
 
-    for line in std::fs::read_to_string(filename).unwrap().lines() {
-        authors.push(line.to_string());
-        log::debug!("Extracted '{}' from list", line)
+        let Some(author) = parse_author(line)
+            .with_context(|| format!("Invalid author in '{}', line {}", filename, line_number))?
+        else {
+            continue;
+        };
+
+        let normalized_name = format::normalize_author_name(&author.name);
+        if let Some(first_line) = known_names.get(&normalized_name) {
+            log::warn!(
+                "'{}' in '{}', line {} is a duplicate of line {} and skipped",
+                author.name,
+                filename,
+                line_number,
+                first_line
+            );
+            continue;
+        }
+        known_names.insert(normalized_name, line_number);
+
+        log::debug!("Extracted '{}' from list", author.name);
+        authors.push(author);
     }
 
     if authors.is_empty() {
@@ -36,3 +72,454 @@ pub fn read_authors(filename: &str) -> Result<Vec<String>> {
 
     Ok(authors)
 }
+
+/// Validate the authors list and optionally fix it. Every issue is logged with its line number.
+///
+/// # Arguments
+///
+/// filename - The file which contains the authors
+/// fix - Rewrite the file sorted and normalized. Malformed lines can not be fixed
+///
+/// # Return
+///
+/// Ok() - The authors list is valid, or was fixed
+/// Err(err) - Some issue was found, or some error occured
+pub fn validate_authors(filename: &str, fix: bool) -> Result<()> {
+    log::trace!("authors::validate_authors()");
+
+    let contents = read_file(filename)?;
+    let issues = check_authors(&contents);
+
+    for issue in &issues {
+        log::warn!("'{}', {}", filename, issue);
+    }
+
+    if fix {
+        let fixed = fix_authors(&contents)
+            .with_context(|| format!("Failed to fix authors file '{}'", filename))?;
+
+        if fixed != contents {
+            write_file(filename, &fixed)?;
+            log::info!("Fixed authors file '{}'", filename);
+        }
+    } else if !issues.is_empty() {
+        anyhow::bail!("Found {} issue(s) in '{}'", issues.len(), filename);
+    } else {
+        log::info!("Authors file '{}' is valid", filename);
+    }
+
+    Ok(())
+}
+
+/// Add an author to the authors list. The file is created if it does not exist yet.
+///
+/// # Arguments
+///
+/// filename - The file which contains the authors
+/// line - The author as line of the authors list, e.g. "King, Stephen | tag=horror"
+///
+/// # Return
+///
+/// Ok() - Successfully added the author
+/// Err(err) - The author is malformed or already listed, or some error occured
+pub fn add_author(filename: &str, line: &str) -> Result<()> {
+    log::trace!("authors::add_author()");
+
+    let Some(author) = parse_author(line)? else {
+        anyhow::bail!("No author given");
+    };
+
+    let mut contents = if std::path::Path::new(filename).exists() {
+        read_file(filename)?
+    } else {
+        String::new()
+    };
+
+    let normalized_name = format::normalize_author_name(&author.name);
+    for (index, known_line) in contents.lines().enumerate() {
+        if let Ok(Some(known)) = parse_author(known_line) {
+            if format::normalize_author_name(&known.name) == normalized_name {
+                anyhow::bail!(
+                    "'{}' is already listed in line {} as '{}'",
+                    author.name,
+                    index + 1,
+                    known.name
+                );
+            }
+        }
+    }
+
+    if !contents.is_empty() && !contents.ends_with('\n') {
+        contents.push('\n');
+    }
+    contents += &(author_line(&author) + "\n");
+
+    write_file(filename, &contents)?;
+    log::info!("Added '{}' to '{}'", author.name, filename);
+
+    Ok(())
+}
+
+/// Remove an author from the authors list. The author is found ignoring case and diacritics, all
+/// other lines are kept as they are.
+///
+/// # Arguments
+///
+/// filename - The file which contains the authors
+/// name - The author to remove, listed as <surname, forename>
+///
+/// # Return
+///
+/// Ok() - Successfully removed the author
+/// Err(err) - The author is not listed, or some error occured
+pub fn remove_author(filename: &str, name: &str) -> Result<()> {
+    log::trace!("authors::remove_author()");
+
+    let contents = read_file(filename)?;
+    let normalized_name = format::normalize_author_name(name);
+
+    let mut removed = Vec::new();
+    let mut kept = String::new();
+
+    for line in contents.lines() {
+        match parse_author(line) {
+            Ok(Some(author)) if format::normalize_author_name(&author.name) == normalized_name => {
+                removed.push(author.name);
+            }
+            _ => kept += &(line.to_string() + "\n"),
+        }
+    }
+
+    if removed.is_empty() {
+        anyhow::bail!("'{}' is not listed in '{}'", name, filename);
+    }
+
+    write_file(filename, &kept)?;
+    log::info!("Removed {:?} from '{}'", removed, filename);
+
+    Ok(())
+}
+
+/// Check the contents of an authors list for byte order marks, surrounding whitespaces, malformed
+/// lines and duplicates (ignoring case and diacritics).
+///
+/// # Arguments
+///
+/// contents - The contents of the authors list
+///
+/// # Return
+///
+/// The issues, each one prefixed by its line number. Empty if the list is valid
+pub fn check_authors(contents: &str) -> Vec<String> {
+    log::trace!("authors::check_authors()");
+
+    let mut issues = Vec::new();
+    let mut known_names = std::collections::HashMap::new();
+
+    if contents.starts_with(BOM) {
+        issues.push("line 1: Byte order mark at the beginning of the file".to_string());
+    }
+
+    for (index, line) in contents.trim_start_matches(BOM).lines().enumerate() {
+        let line_number = index + 1;
+
+        if line != line.trim() {
+            issues.push(format!(
+                "line {}: Leading or trailing whitespaces",
+                line_number
+            ));
+        }
+
+        match parse_author(line) {
+            Ok(Some(author)) => {
+                let normalized_name = format::normalize_author_name(&author.name);
+
+                match known_names.get(&normalized_name) {
+                    Some(first_line) => issues.push(format!(
+                        "line {}: '{}' is a duplicate of line {}",
+                        line_number, author.name, first_line
+                    )),
+                    None => {
+                        known_names.insert(normalized_name, line_number);
+                    }
+                }
+            }
+            Ok(None) => {}
+            Err(err) => issues.push(format!("line {}: {:#}", line_number, err)),
+        }
+    }
+
+    issues
+}
+
+/// Sort and normalize the contents of an authors list. Byte order marks, surrounding whitespaces,
+/// blank lines and duplicates are removed, and the authors are sorted by name. Comment lines stay
+/// in front of the author following them.
+///
+/// # Arguments
+///
+/// contents - The contents of the authors list
+///
+/// # Return
+///
+/// Ok(String) - The fixed contents
+/// Err(err) - The list contains malformed lines
+pub fn fix_authors(contents: &str) -> Result<String> {
+    log::trace!("authors::fix_authors()");
+
+    let mut entries: Vec<(String, Vec<String>, String)> = Vec::new();
+    let mut comments = Vec::new();
+
+    for (index, line) in contents.trim_start_matches(BOM).lines().enumerate() {
+        let line = line.trim();
+
+        if line.starts_with('#') {
+            comments.push(line.to_string());
+            continue;
+        }
+
+        let Some(author) =
+            parse_author(line).with_context(|| format!("Malformed line {}", index + 1))?
+        else {
+            continue;
+        };
+
+        let normalized_name = format::normalize_author_name(&author.name);
+        if entries
+            .iter()
+            .any(|(known, _, _)| known == &normalized_name)
+        {
+            log::info!("Removed duplicate '{}' in line {}", author.name, index + 1);
+            continue;
+        }
+
+        // keep the comment at the end of the line
+        let mut fixed_line = author_line(&author);
+        if let Some((_, comment)) = line.split_once('#') {
+            fixed_line = fixed_line + " # " + comment.trim();
+        }
+
+        entries.push((normalized_name, std::mem::take(&mut comments), fixed_line));
+    }
+
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut fixed = String::new();
+    for (_, entry_comments, line) in entries {
+        for comment in entry_comments {
+            fixed += &(comment + "\n");
+        }
+        fixed += &(line + "\n");
+    }
+    for comment in comments {
+        fixed += &(comment + "\n");
+    }
+
+    Ok(fixed)
+}
+
+/// Parse a single line of the authors list.
+///
+/// # Arguments
+///
+/// line - The line to parse, e.g. "Bachman, Richard = King, Stephen | tag=horror"
+///
+/// # Return
+///
+/// Ok(Some(Author)) - The author of the line
+/// Ok(None) - The line is blank or a comment
+/// Err(err) - The line is malformed
+pub fn parse_author(line: &str) -> Result<Option<Author>> {
+    log::trace!("authors::parse_author()");
+
+    // remove a byte order mark and the comment first
+    let line = line
+        .trim_start_matches(BOM)
+        .split('#')
+        .next()
+        .unwrap_or_default()
+        .trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+
+    let mut parts = line.split('|');
+    let names = parts.next().unwrap_or_default();
+
+    // a pseudonym is followed by the author its releases are reported for
+    let mut author = match names.split_once('=') {
+        Some((pseudonym, real_name)) => {
+            let mut author = Author::create(parse_name(pseudonym)?);
+            author.alias_of = Some(parse_name(real_name)?);
+            author
+        }
+        None => Author::create(parse_name(names)?),
+    };
+
+    for option in parts {
+        let Some((key, value)) = option.split_once('=') else {
+            anyhow::bail!("Option '{}' is not given as <key=value>", option.trim());
+        };
+        let (key, value) = (key.trim(), value.trim());
+
+        if value.is_empty() {
+            anyhow::bail!("No value given for option '{}'", key);
+        }
+
+        let already_set = match key {
+            "languages" | "language" => author.languages.replace(split_list(value)).is_some(),
+            "formats" => author
+                .formats
+                .replace(
+                    split_list(value)
+                        .iter()
+                        .map(|format| parse_format(format))
+                        .collect::<Result<_>>()?,
+                )
+                .is_some(),
+            "tag" => author.tag.replace(value.to_string()).is_some(),
+            _ => anyhow::bail!(
+                "Unknown option '{}', expected languages, formats or tag",
+                key
+            ),
+        };
+
+        if already_set {
+            anyhow::bail!("Option '{}' is given more than once", key);
+        }
+    }
+
+    if author
+        .languages
+        .as_ref()
+        .is_some_and(|languages| languages.is_empty())
+    {
+        anyhow::bail!("No language given for option 'languages'");
+    }
+    if author
+        .formats
+        .as_ref()
+        .is_some_and(|formats| formats.is_empty())
+    {
+        anyhow::bail!("No book format given for option 'formats'");
+    }
+
+    Ok(Some(author))
+}
+
+/// Build the normalized line of an author in the authors list.
+///
+/// # Arguments
+///
+/// author - The author
+///
+/// # Return
+///
+/// The line of the author, e.g. "Bachman, Richard = King, Stephen | tag=horror"
+pub fn author_line(author: &Author) -> String {
+    let mut line = author.name.clone();
+
+    if let Some(alias_of) = &author.alias_of {
+        line = line + " = " + alias_of;
+    }
+    if let Some(languages) = &author.languages {
+        line = line + " | languages=" + &languages.join(",");
+    }
+    if let Some(formats) = &author.formats {
+        let formats: Vec<String> = formats.iter().map(|format| format.to_string()).collect();
+        line = line + " | formats=" + &formats.join(",");
+    }
+    if let Some(tag) = &author.tag {
+        line = line + " | tag=" + tag;
+    }
+
+    line
+}
+
+/// Read the contents of the authors list.
+///
+/// # Arguments
+///
+/// filename - The file which contains the authors
+///
+/// # Return
+///
+/// Ok(String) - The contents of the file
+/// Err(err) - Some error occured
+fn read_file(filename: &str) -> Result<String> {
+    // first of all, check whether file exists or not
+    if !std::path::Path::new(filename).exists() {
+        anyhow::bail!("File '{}' not found!", filename);
+    }
+
+    std::fs::read_to_string(filename)
+        .with_context(|| format!("Failed to read authors file '{}'", filename))
+}
+
+/// Write the authors list. The contents are written to a temporary file first, which replaces the
+/// authors list afterwards, so the list is never left half-written.
+///
+/// # Arguments
+///
+/// filename - The file which contains the authors
+/// contents - The contents to write
+///
+/// # Return
+///
+/// Ok() - Successfully written the file
+/// Err(err) - Some error occured
+fn write_file(filename: &str, contents: &str) -> Result<()> {
+    let temporary_file = filename.to_owned() + ".tmp";
+
+    std::fs::write(&temporary_file, contents)
+        .with_context(|| format!("Failed to write temporary file '{}'", temporary_file))?;
+    std::fs::rename(&temporary_file, filename)
+        .with_context(|| format!("Failed to write authors file '{}'", filename))
+}
+
+/// Check and normalize an author name listed as <surname, forename>. Whitespaces are reduced to
+/// single spaces.
+///
+/// # Arguments
+///
+/// name - The author name
+///
+/// # Return
+///
+/// Ok(String) - The normalized author name
+/// Err(err) - The name is not listed as <surname, forename>
+fn parse_name(name: &str) -> Result<String> {
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    match name.split_once(", ") {
+        Some((surname, forename))
+            if !surname.is_empty() && !forename.is_empty() && !forename.contains(',') =>
+        {
+            Ok(name)
+        }
+        _ => anyhow::bail!(
+            "'{}' is not listed as <surname, forename> and can not be rearranged",
+            name
+        ),
+    }
+}
+
+/// Split the comma-separated value of an option into its trimmed, non-empty entries.
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|entry| entry.trim().to_string())
+        .filter(|entry| !entry.is_empty())
+        .collect()
+}
+
+/// Parse a book format of the authors list, e.g. "Taschenbuch" or "Hörbuch". Case is ignored.
+fn parse_format(format: &str) -> Result<MediaFormat> {
+    <MediaFormat as clap::ValueEnum>::from_str(format, true).map_err(|_| {
+        anyhow::anyhow!(
+            "Unknown book format '{}', expected Hardcover, Taschenbuch, E-Book, Hörbuch or \
+             Hörbuch-Download",
+            format
+        )
+    })
+}
@@ -1,21 +1,276 @@
 //! This module contains the arguments passed via command line.
 
+use crate::customtypes::{Locale, MediaFormat, OutputFormat, ReissueFilter};
+
+/// Get informed about upcoming book releases of your favourite authors
 #[derive(clap::Parser, Debug)]
 #[command(version, about, long_about = None)]
 pub struct Args {
-    /// Path to the file containing your authors
-    #[arg(short, long, default_value = "/home/authors")]
-    pub authors_file: String,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Path to the TOML config file. Arguments passed via command line override its entries
+    #[arg(long, global = true)]
+    pub config: Option<String>,
+
+    /// Path to the file containing your authors [default: /home/authors]
+    #[arg(short, long, global = true)]
+    pub authors_file: Option<String>,
+
+    /// Destination path the release file has to be stored. Without a destination path or with "-",
+    /// the release file is printed to stdout instead (not supported by "show") [default: -]
+    #[arg(short, long, global = true)]
+    pub dest_release: Option<String>,
+
+    /// Name of the release file [default: releases]
+    #[arg(short, long, global = true)]
+    pub release_file: Option<String>,
+
+    /// Log level (off, warn, error, info, debug, trace) [default: info]
+    #[arg(short, long, global = true)]
+    pub loglevel: Option<String>,
+
+    /// Arguments of searching for upcoming releases, if they are given before the "fetch" command
+    /// or no command is given
+    #[command(flatten)]
+    pub fetch: FetchArgs,
+}
+
+/// The arguments of searching for upcoming releases.
+#[derive(clap::Args, Debug, Clone)]
+pub struct FetchArgs {
+    /// Format of the release file
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+
+    /// Language of the dates, headings and labels of the text and iCalendar formats
+    #[arg(long, value_enum, default_value_t = Locale::De)]
+    pub locale: Locale,
+
+    /// Path to the state file. If given, the releases are compared with the previous run and the
+    /// changes are reported in "<release file>.changes"
+    #[arg(long)]
+    pub state_file: Option<String>,
+
+    /// Only write new and changed releases into the release file (requires a state file)
+    #[arg(long, requires = "state_file")]
+    pub only_changes: bool,
+
+    /// Whether reissues, box sets and special editions are part of the release file
+    #[arg(long, value_enum, default_value_t = ReissueFilter::Hide)]
+    pub reissues: ReissueFilter,
+
+    /// Path to the history file. If given, every title found is remembered, and an already
+    /// published title found again is detected as reissue
+    #[arg(long)]
+    pub history_file: Option<String>,
+
+    /// Fetch the product page of every release to add the number of pages, series, translator,
+    /// original title, description and exact publication date
+    #[arg(long)]
+    pub details: bool,
+
+    /// Path to the details cache. If given, the product pages of unchanged search results are not
+    /// fetched again (requires --details)
+    #[arg(long, requires = "details")]
+    pub details_cache: Option<String>,
+
+    /// Ordered chain of release sources (weltbild, thalia, hugendubel)
+    #[arg(
+        short,
+        long,
+        value_delimiter = ',',
+        default_value = "weltbild,thalia,hugendubel"
+    )]
+    pub sources: Vec<String>,
+
+    /// Release year filter of Weltbild, "0" means upcoming releases [default: 0]
+    #[arg(long)]
+    pub release_year: Option<String>,
+
+    /// Media type node of Weltbild, empty for all media types [default: derived from the formats,
+    /// e.g. /buecher]
+    #[arg(long)]
+    pub media_type: Option<String>,
+
+    /// Languages of Weltbild, each searched separately. Empty for all languages
+    /// [default: /language/ger]
+    #[arg(long, alias = "language", value_delimiter = ',')]
+    pub languages: Option<Vec<String>>,
+
+    /// Accepted book formats [default: taschenbuch,hardcover]
+    #[arg(long, value_enum, value_delimiter = ',', ignore_case = true)]
+    pub formats: Option<Vec<MediaFormat>>,
+
+    /// Maximum number of upcoming releases per author and release source, 0 for no limit. A
+    /// warning is logged if more are found [default: 10]
+    #[arg(long)]
+    pub max_results: Option<usize>,
+
+    /// Maximum number of search result pages followed per author, release source and language
+    /// [default: 3]
+    #[arg(long)]
+    pub max_pages: Option<usize>,
+
+    /// Read saved search result pages from this directory instead of sending requests
+    #[arg(short, long)]
+    pub offline_dir: Option<String>,
+
+    /// Maximum number of authors looked up concurrently
+    #[arg(short, long, default_value_t = 4)]
+    pub concurrency: usize,
+
+    /// Maximum number of requests per second sent to the same webpage (0 disables the limit)
+    #[arg(short = 'q', long, default_value_t = 1.0, value_parser = parse_rate)]
+    pub requests_per_second: f64,
+
+    /// Maximum number of retries of a failed request
+    #[arg(long, default_value_t = 3)]
+    pub retries: u32,
+
+    /// Delay in milliseconds before the first retry. It doubles with every further retry
+    #[arg(long, default_value_t = 1000)]
+    pub retry_delay: u64,
+
+    /// Timeout in seconds of a single request
+    #[arg(long, default_value_t = 30)]
+    pub timeout: u64,
+}
+
+impl Args {
+    /// Parse the arguments passed via command line and exit with the usage on invalid arguments.
+    ///
+    /// # Return
+    ///
+    /// Args - The parsed arguments
+    pub fn parse_args() -> Self {
+        log::trace!("cli::parse_args()");
+
+        Self::try_parse_args(std::env::args_os()).unwrap_or_else(|err| err.exit())
+    }
+
+    /// Parse the given arguments. The arguments of searching for upcoming releases are given
+    /// either before or after the "fetch" command, but not on both sides.
+    ///
+    /// # Arguments
+    ///
+    /// itr - The arguments, starting with the name of the application
+    ///
+    /// # Return
+    ///
+    /// Ok(Args) - The parsed arguments
+    /// Err(clap::Error) - The arguments are invalid
+    pub fn try_parse_args<I, T>(itr: I) -> Result<Self, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<std::ffi::OsString> + Clone,
+    {
+        let mut command = <Self as clap::CommandFactory>::command();
+        let matches = command.try_get_matches_from_mut(itr)?;
+        let mut args = <Self as clap::FromArgMatches>::from_arg_matches(&matches)?;
+
+        if let (Some(Command::Fetch(fetch)), Some(("fetch", fetch_matches))) =
+            (&mut args.command, matches.subcommand())
+        {
+            let ids: Vec<clap::Id> =
+                <FetchArgs as clap::Args>::augment_args(clap::Command::new("fetch"))
+                    .get_arguments()
+                    .map(|arg| arg.get_id().clone())
+                    .collect();
+            let given = |matches: &clap::ArgMatches| {
+                ids.iter().any(|id| {
+                    matches.value_source(id.as_str())
+                        == Some(clap::parser::ValueSource::CommandLine)
+                })
+            };
+
+            match (given(&matches), given(fetch_matches)) {
+                (true, true) => {
+                    return Err(command.error(
+                        clap::error::ErrorKind::ArgumentConflict,
+                        "the arguments of searching are given either before or after \"fetch\", not on both sides",
+                    ))
+                }
+                (true, false) => std::mem::swap(fetch.as_mut(), &mut args.fetch),
+                _ => {}
+            }
+        }
+
+        Ok(args)
+    }
+
+    /// Get the arguments of searching for upcoming releases, if the "fetch" command or no command
+    /// is given.
+    ///
+    /// # Return
+    ///
+    /// Some(&FetchArgs) - The arguments of searching for upcoming releases
+    /// None - Another command is given
+    pub fn fetch_args(&self) -> Option<&FetchArgs> {
+        match &self.command {
+            Some(Command::Fetch(fetch)) => Some(fetch),
+            None => Some(&self.fetch),
+            Some(_) => None,
+        }
+    }
+}
+
+/// The commands of the application.
+#[derive(clap::Subcommand, Debug)]
+pub enum Command {
+    /// Search for upcoming releases and write them into the release file (default)
+    Fetch(Box<FetchArgs>),
+    /// Manage the authors file
+    Authors {
+        #[command(subcommand)]
+        command: AuthorsCommand,
+    },
+    /// Print the release file of the last run
+    Show,
+    /// Check the authors file for malformed lines, duplicates, byte order marks and surrounding
+    /// whitespaces
+    Validate {
+        /// Rewrite the authors file sorted and normalized. Malformed lines have to be fixed by hand
+        #[arg(long)]
+        fix: bool,
+    },
+}
+
+/// The commands to manage the authors file.
+#[derive(clap::Subcommand, Debug)]
+pub enum AuthorsCommand {
+    /// Add an author, e.g. "King, Stephen | tag=horror"
+    Add {
+        /// The author as line of the authors file
+        author: String,
+    },
+    /// Remove an author, e.g. "King, Stephen"
+    Remove {
+        /// The author, listed as <surname, forename>
+        author: String,
+    },
+    /// Print all authors
+    List,
+}
 
-    /// Destination path the release file has to be stored
-    #[arg(short, long, default_value = "/home")]
-    pub dest_release: String,
+/// Parse a rate of requests per second, which has to be a finite number of zero or more.
+///
+/// # Arguments
+///
+/// value - The rate as passed via command line
+///
+/// # Return
+///
+/// Ok(f64) - The rate
+/// Err(String) - The rate is no number, negative or infinite
+fn parse_rate(value: &str) -> Result<f64, String> {
+    let rate: f64 = value
+        .parse()
+        .map_err(|_| format!("'{}' is no number", value))?;
 
-    /// Name of the release file
-    #[arg(short, long, default_value = "releases")]
-    pub release_file: String,
+    if !rate.is_finite() || rate < 0.0 {
+        return Err(format!("'{}' is no finite number of zero or more", value));
+    }
 
-    /// Log level (off, warn, error, info, debug, trace)
-    #[arg(short, long, default_value = "info")]
-    pub loglevel: String,
+    Ok(rate)
 }
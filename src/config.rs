@@ -0,0 +1,209 @@
+//! This module contains the configuration of the application. It is read from a TOML file and can
+//! be overridden via command line.
+
+use crate::cli::Args;
+use crate::customtypes::{Author, MediaFormat};
+use crate::releases;
+use anyhow::{Context, Result};
+
+/// Custom data type to collect the configuration.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Path to the file containing your authors
+    pub authors_file: String,
+    /// Destination path the release file has to be stored, "-" prints it to stdout instead
+    pub dest_release: String,
+    /// Name of the release file
+    pub release_file: String,
+    /// Log level (off, warn, error, info, debug, trace)
+    pub loglevel: String,
+    /// What to search for
+    pub search: SearchConfig,
+}
+
+/// Custom data type to collect the search parameters.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SearchConfig {
+    /// Release year filter of Weltbild ("0" means upcoming releases)
+    pub release_year: String,
+    /// Media type node of Weltbild, e.g. "/buecher". Derived from the accepted book formats if
+    /// not given
+    pub media_type: Option<String>,
+    /// Languages of Weltbild, e.g. "/language/ger". Every language is searched separately, an
+    /// empty list searches all languages at once
+    pub languages: Vec<String>,
+    /// Accepted book formats. Search results in other formats are skipped
+    pub formats: Vec<MediaFormat>,
+    /// Maximum number of upcoming releases per author and release source, 0 for no limit
+    pub max_results: usize,
+    /// Maximum number of search result pages followed per author, release source and language
+    pub max_pages: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            authors_file: "/home/authors".to_string(),
+            dest_release: releases::STDOUT.to_string(),
+            release_file: "releases".to_string(),
+            loglevel: "info".to_string(),
+            search: SearchConfig::default(),
+        }
+    }
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        SearchConfig {
+            release_year: "0".to_string(),
+            media_type: None,
+            languages: vec!["/language/ger".to_string()],
+            formats: vec![MediaFormat::Taschenbuch, MediaFormat::Hardcover],
+            max_results: 10,
+            max_pages: 3,
+        }
+    }
+}
+
+impl SearchConfig {
+    /// Get the search parameters for an author. The languages and the accepted book formats of the
+    /// authors list override the configured ones.
+    ///
+    /// # Arguments
+    ///
+    /// author - The author to search for
+    ///
+    /// # Return
+    ///
+    /// The search parameters of the author
+    pub fn for_author(&self, author: &Author) -> SearchConfig {
+        let mut search = self.clone();
+
+        if let Some(languages) = &author.languages {
+            search.languages = languages.clone();
+        }
+        if let Some(formats) = &author.formats {
+            search.formats = formats.clone();
+        }
+
+        search
+    }
+
+    /// Get the media type node of Weltbild. If none is configured, it is derived from the accepted
+    /// book formats, e.g. "/buecher" if only printed books are accepted. If the formats are listed
+    /// under different nodes, no node is used.
+    ///
+    /// # Return
+    ///
+    /// The media type node. Empty for all media types
+    pub fn media_type(&self) -> String {
+        if let Some(media_type) = &self.media_type {
+            return media_type.clone();
+        }
+
+        let mut media_types = self.formats.iter().map(|format| format.media_type());
+        match media_types.next() {
+            Some(first) if media_types.all(|media_type| media_type == first) => first.to_string(),
+            _ => String::new(),
+        }
+    }
+
+    /// Check whether only printed books are accepted.
+    pub fn only_printed(&self) -> bool {
+        self.formats.iter().all(|format| format.is_printed())
+    }
+}
+
+/// Read the configuration from a TOML file. Missing entries get their default value.
+///
+/// # Arguments
+///
+/// path - Path to the TOML file
+///
+/// # Return
+///
+/// Ok(Config) - The configuration
+/// Err(err) - Some error occured
+pub fn read_config(path: &str) -> Result<Config> {
+    log::trace!("config::read_config()");
+
+    // first of all, check whether file exists or not
+    if !std::path::Path::new(path).exists() {
+        anyhow::bail!("File '{}' not found!", path);
+    }
+
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file '{}'", path))?;
+
+    toml::from_str(&contents).with_context(|| format!("Failed to parse config file '{}'", path))
+}
+
+/// Create the configuration of this run. It is read from the config file passed via command line,
+/// if any, and every argument passed via command line overrides the respective entry.
+///
+/// # Arguments
+///
+/// args - The arguments passed via command line
+///
+/// # Return
+///
+/// Ok(Config) - The configuration
+/// Err(err) - Some error occured
+pub fn create_config(args: &Args) -> Result<Config> {
+    log::trace!("config::create_config()");
+
+    let mut config = match &args.config {
+        Some(path) => read_config(path)?,
+        None => Config::default(),
+    };
+
+    if let Some(authors_file) = &args.authors_file {
+        config.authors_file = authors_file.clone();
+    }
+    if let Some(dest_release) = &args.dest_release {
+        config.dest_release = dest_release.clone();
+    }
+    if let Some(release_file) = &args.release_file {
+        config.release_file = release_file.clone();
+    }
+    if let Some(loglevel) = &args.loglevel {
+        config.loglevel = loglevel.clone();
+    }
+    // the search parameters are only given when searching for upcoming releases
+    if let Some(fetch) = args.fetch_args() {
+        if let Some(release_year) = &fetch.release_year {
+            config.search.release_year = release_year.clone();
+        }
+        if let Some(media_type) = &fetch.media_type {
+            config.search.media_type = Some(media_type.clone());
+        }
+        if let Some(languages) = &fetch.languages {
+            // an empty value searches all languages
+            config.search.languages = languages
+                .iter()
+                .filter(|language| !language.is_empty())
+                .cloned()
+                .collect();
+        }
+        if let Some(formats) = &fetch.formats {
+            config.search.formats = formats.clone();
+        }
+        if let Some(max_results) = fetch.max_results {
+            config.search.max_results = max_results;
+        }
+        if let Some(max_pages) = fetch.max_pages {
+            config.search.max_pages = max_pages;
+        }
+    }
+
+    if config.search.formats.is_empty() {
+        anyhow::bail!("No book format configured!");
+    }
+    if config.search.max_pages == 0 {
+        anyhow::bail!("At least one search result page has to be searched!");
+    }
+
+    Ok(config)
+}
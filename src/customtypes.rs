@@ -1,11 +1,103 @@
 //! This module contains custom-defined data types.
 
+/// Custom data type of an author of the authors list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Author {
+    /// The name searched for, listed as <surname, forename>
+    pub name: String,
+    /// The author the releases are reported for if the name is a pseudonym, listed as
+    /// <surname, forename>
+    pub alias_of: Option<String>,
+    /// Languages overriding the configured ones for this author
+    pub languages: Option<Vec<String>>,
+    /// Accepted book formats overriding the configured ones for this author
+    pub formats: Option<Vec<MediaFormat>>,
+    /// Tag to group the authors, e.g. "horror"
+    pub tag: Option<String>,
+}
+
+impl Author {
+    pub fn create(name: String) -> Self {
+        Author {
+            name,
+            alias_of: None,
+            languages: None,
+            formats: None,
+            tag: None,
+        }
+    }
+
+    /// Get the author the releases are reported for, listed as <surname, forename>.
+    pub fn release_author(&self) -> &str {
+        self.alias_of.as_deref().unwrap_or(&self.name)
+    }
+}
+
 /// Custom data type to collect the upcoming releases.
-#[derive(Debug)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct UpcomingRelease {
     pub author: String,
     pub title: String,
+    /// All contributors of the release as listed on the release source, including the author
+    #[serde(default)]
+    pub contributors: Vec<String>,
+    #[serde(
+        serialize_with = "serialize_date",
+        deserialize_with = "deserialize_date"
+    )]
     pub date: chrono::DateTime<chrono::Utc>,
+    /// Name of the release source the release was found on
+    #[serde(default)]
+    pub source: String,
+    /// URL of the product page of the release
+    #[serde(default)]
+    pub url: String,
+    /// URL of the cover image of the release (empty if unknown)
+    #[serde(default)]
+    pub cover_url: String,
+    /// Format of the release, e.g. "Taschenbuch" or "Hörbuch" (empty if unknown)
+    #[serde(default)]
+    pub format: String,
+    /// Price of the release as listed on the release source, e.g. "26.00 €" (empty if unknown)
+    #[serde(default)]
+    pub price: String,
+    /// Publisher of the release, e.g. "Heyne" (empty if unknown)
+    #[serde(default)]
+    pub publisher: String,
+    /// Number of pages as listed on the product page (None if unknown)
+    #[serde(default)]
+    pub pages: Option<u32>,
+    /// Name of the series the release belongs to, e.g. "David Hunter" (empty if none)
+    #[serde(default)]
+    pub series: String,
+    /// Volume of the release in its series (None if unknown)
+    #[serde(default)]
+    pub volume: Option<u32>,
+    /// Translator(s) of the release as listed on the product page (empty if unknown)
+    #[serde(default)]
+    pub translator: String,
+    /// Title of the original edition of a translation as listed on the product page (empty if
+    /// unknown)
+    #[serde(default)]
+    pub original_title: String,
+    /// Description blurb of the product page (empty if unknown)
+    #[serde(default)]
+    pub description: String,
+    /// Tag of the author in the authors list (empty if none)
+    #[serde(default)]
+    pub tag: String,
+    /// Language code of the release, e.g. "ger" (empty if unknown)
+    #[serde(default)]
+    pub language: String,
+    /// ISBN-13 of the release, e.g. "9783734113246" (empty if unknown)
+    #[serde(default)]
+    pub isbn: String,
+    /// Further editions of the same work in other formats, languages or from other release sources
+    #[serde(default)]
+    pub editions: Vec<Edition>,
+    /// Reason the release is considered a reissue, e.g. "Sonderausgabe" (empty for a new release)
+    #[serde(default)]
+    pub reissue: String,
 }
 
 impl UpcomingRelease {
@@ -13,7 +105,245 @@ impl UpcomingRelease {
         UpcomingRelease {
             author,
             title,
+            contributors: Vec::new(),
             date,
+            source: String::new(),
+            url: String::new(),
+            cover_url: String::new(),
+            format: String::new(),
+            price: String::new(),
+            publisher: String::new(),
+            pages: None,
+            series: String::new(),
+            volume: None,
+            translator: String::new(),
+            original_title: String::new(),
+            description: String::new(),
+            tag: String::new(),
+            language: String::new(),
+            isbn: String::new(),
+            editions: Vec::new(),
+            reissue: String::new(),
         }
     }
 }
+
+/// Custom data type of a further edition of an upcoming release.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Edition {
+    pub title: String,
+    #[serde(
+        serialize_with = "serialize_date",
+        deserialize_with = "deserialize_date"
+    )]
+    pub date: chrono::DateTime<chrono::Utc>,
+    /// Language code of the edition, e.g. "eng" (empty if unknown)
+    pub language: String,
+    /// Name of the release source the edition was found on
+    pub source: String,
+    /// URL of the product page of the edition
+    pub url: String,
+    /// Format of the edition, e.g. "Taschenbuch" or "Hörbuch"
+    pub format: String,
+    /// ISBN-13 of the edition (empty if unknown)
+    #[serde(default)]
+    pub isbn: String,
+}
+
+/// Serialize a release date as ISO 8601 date (YYYY-MM-DD).
+fn serialize_date<S>(date: &chrono::DateTime<chrono::Utc>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&date.format("%Y-%m-%d").to_string())
+}
+
+/// Deserialize a release date from an ISO 8601 date (YYYY-MM-DD).
+fn deserialize_date<'de, D>(deserializer: D) -> Result<chrono::DateTime<chrono::Utc>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let date_str = <String as serde::Deserialize>::deserialize(deserializer)?;
+
+    chrono::NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
+        .map(|date| date.and_hms_opt(0, 0, 0).unwrap().and_utc())
+        .map_err(serde::de::Error::custom)
+}
+
+/// Custom data type of a title found in a previous run.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct KnownTitle {
+    pub author: String,
+    pub title: String,
+    /// The earliest release date the title was found with
+    #[serde(
+        serialize_with = "serialize_date",
+        deserialize_with = "deserialize_date"
+    )]
+    pub date: chrono::DateTime<chrono::Utc>,
+    /// ISBN of the release the title was found with (empty if unknown)
+    #[serde(default)]
+    pub isbn: String,
+    /// Product page of the release the title was found with (empty if unknown)
+    #[serde(default)]
+    pub url: String,
+}
+
+/// Custom data type of the details found on the product page of a release.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ProductDetails {
+    /// Number of pages (None if not found)
+    pub pages: Option<u32>,
+    /// Name of the series (empty if not found)
+    pub series: String,
+    /// Volume in the series (None if not found)
+    pub volume: Option<u32>,
+    /// Translator(s) (empty if not found)
+    pub translator: String,
+    /// Title of the original edition (empty if not found)
+    #[serde(default)]
+    pub original_title: String,
+    /// Description blurb (empty if not found)
+    pub description: String,
+    /// Exact publication date as ISO 8601 date (empty if not found)
+    pub date: String,
+}
+
+/// Custom data type of a product page in the details cache. The details are reused as long as the
+/// search result of the release is unchanged.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CachedDetails {
+    /// URL of the product page
+    pub url: String,
+    /// Title of the search result
+    pub title: String,
+    /// Release date of the search result
+    #[serde(
+        serialize_with = "serialize_date",
+        deserialize_with = "deserialize_date"
+    )]
+    pub date: chrono::DateTime<chrono::Utc>,
+    /// Price of the search result
+    pub price: String,
+    pub details: ProductDetails,
+}
+
+/// Custom data type to select whether reissues are part of the release file.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReissueFilter {
+    /// Leave out reissues, box sets and special editions
+    Hide,
+    /// List reissues, box sets and special editions, marked as such
+    Show,
+}
+
+/// Custom data type to classify how a release changed since the last run.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeKind {
+    /// The release was not known before
+    New,
+    /// The release date changed. Contains the previous release date
+    DateChanged(chrono::DateTime<chrono::Utc>),
+    /// The title changed. Contains the previous title
+    TitleChanged(String),
+    /// The release was known before, but is not found anymore
+    Disappeared,
+}
+
+/// Custom data type to collect a changed release.
+#[derive(Debug, Clone)]
+pub struct ReleaseChange {
+    pub kind: ChangeKind,
+    /// The current release, or the previous one if it disappeared
+    pub release: UpcomingRelease,
+}
+
+/// Custom data type of the format of a release.
+#[derive(
+    clap::ValueEnum, serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq,
+)]
+pub enum MediaFormat {
+    /// Hardcover, listed as "Buch" or "gebunden" by the release sources
+    #[value(alias = "buch")]
+    #[serde(alias = "Buch")]
+    Hardcover,
+    /// Paperback
+    Taschenbuch,
+    /// E-Book
+    #[value(name = "e-book", alias = "ebook")]
+    #[serde(rename = "E-Book")]
+    EBook,
+    /// Audio book on CD
+    #[value(name = "hörbuch", alias = "hoerbuch")]
+    #[serde(rename = "Hörbuch")]
+    Hoerbuch,
+    /// Audio book to download
+    #[value(name = "hörbuch-download", alias = "hoerbuch-download")]
+    #[serde(rename = "Hörbuch-Download")]
+    HoerbuchDownload,
+}
+
+impl MediaFormat {
+    /// Check whether the format is a printed book.
+    pub fn is_printed(&self) -> bool {
+        matches!(self, MediaFormat::Hardcover | MediaFormat::Taschenbuch)
+    }
+
+    /// Get the media type node of Weltbild the format is listed under.
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            MediaFormat::Hardcover | MediaFormat::Taschenbuch => "/buecher",
+            MediaFormat::EBook => "/ebooks",
+            MediaFormat::Hoerbuch | MediaFormat::HoerbuchDownload => "/hoerbuecher",
+        }
+    }
+}
+
+impl std::fmt::Display for MediaFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = match self {
+            MediaFormat::Hardcover => "Hardcover",
+            MediaFormat::Taschenbuch => "Taschenbuch",
+            MediaFormat::EBook => "E-Book",
+            MediaFormat::Hoerbuch => "Hörbuch",
+            MediaFormat::HoerbuchDownload => "Hörbuch-Download",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Custom data type to select the format of the release file.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable list of releases, grouped by release date
+    Text,
+    /// Comma-separated values (RFC 4180) with the columns author, title, date, source, url,
+    /// language, editions, format, reissue, isbn, publisher, price, cover_url, series, volume, pages,
+    /// translator and description
+    Csv,
+    /// A single JSON document containing the schema version and all releases
+    Json,
+    /// Newline-delimited JSON, one release per line
+    Ndjson,
+    /// iCalendar (RFC 5545) with one all-day event per release
+    Ics,
+}
+
+/// Custom data type to select the language of the human-readable release file.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    /// German dates, e.g. "30. September 2024"
+    De,
+    /// English dates, e.g. "September 30, 2024"
+    En,
+    /// French dates and headings, e.g. "30 septembre 2024"
+    Fr,
+}
+
+/// Custom data type to collect the result of scraping the upcoming releases of all authors.
+#[derive(Debug, Default)]
+pub struct ScrapeResult {
+    pub releases: Vec<UpcomingRelease>,
+    /// Authors which could not be checked, together with the reason
+    pub failed_authors: Vec<(Author, String)>,
+}
@@ -0,0 +1,351 @@
+//! This module enriches the upcoming releases with the details of their product pages, e.g. the
+//! number of pages or the translator.
+
+use crate::customtypes::{CachedDetails, ProductDetails, UpcomingRelease};
+use crate::fetcher::Fetcher;
+use anyhow::{Context, Result};
+use futures::StreamExt;
+
+/// Labels of the number of pages on a product page.
+const PAGES: [&str; 3] = ["Seitenzahl", "Seitenanzahl", "Anzahl Seiten"];
+
+/// Labels of the series on a product page.
+const SERIES: [&str; 3] = ["Reihe", "Serie", "Buchreihe"];
+
+/// Labels of the volume in the series on a product page.
+const VOLUME: [&str; 2] = ["Band", "Bd."];
+
+/// Labels of the translator on a product page.
+const TRANSLATOR: [&str; 4] = ["Übersetzer", "Übersetzerin", "Übersetzung", "Übersetzt von"];
+
+/// Labels of the title of the original edition on a product page.
+const ORIGINAL_TITLE: [&str; 3] = [
+    "Originaltitel",
+    "Original-Titel",
+    "Titel der Originalausgabe",
+];
+
+/// Labels of the publication date on a product page.
+const DATE: [&str; 4] = [
+    "Erscheinungsdatum",
+    "Erscheinungstermin",
+    "Erstverkaufstag",
+    "Erscheint am",
+];
+
+/// CSS selectors of the description blurb, in order of preference. Meta tags hold the blurb in
+/// their "content" attribute.
+const DESCRIPTION: [&str; 4] = [
+    "[itemprop=\"description\"]",
+    "div.product-description",
+    "meta[property=\"og:description\"]",
+    "meta[name=\"description\"]",
+];
+
+/// Parse the details of a product page. The details are listed as "label: value", either in a
+/// single text or as label followed by the value, e.g. in a definition list. Labels are compared
+/// case-insensitively.
+///
+/// # Arguments
+///
+/// html_content - The HTML content of the product page
+///
+/// # Return
+///
+/// The details found on the product page. Details not found are left empty
+pub fn parse_product_page(html_content: &str) -> ProductDetails {
+    log::trace!("details::parse_product_page()");
+
+    let document = scraper::Html::parse_document(html_content);
+
+    // every text node is a line, so labels and values in separate elements are kept apart
+    let lines: Vec<&str> = document
+        .root_element()
+        .text()
+        .map(|text| text.trim())
+        .filter(|text| !text.is_empty())
+        .collect();
+
+    ProductDetails {
+        pages: label_value(&lines, &PAGES).and_then(|value| first_number(&value)),
+        series: label_value(&lines, &SERIES).unwrap_or_default(),
+        volume: label_value(&lines, &VOLUME).and_then(|value| first_number(&value)),
+        translator: label_value(&lines, &TRANSLATOR).unwrap_or_default(),
+        original_title: label_value(&lines, &ORIGINAL_TITLE).unwrap_or_default(),
+        description: description(&document).unwrap_or_default(),
+        date: label_value(&lines, &DATE)
+            .and_then(|value| german_date(&value))
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// Fetch the product page of every release and add its details to the release. The details of
+/// an unchanged search result (same product page, title, date and price) are taken from the cache
+/// instead. Releases without product page or whose product page could not be fetched are kept as
+/// they are.
+///
+/// # Arguments
+///
+/// releases - The upcoming releases
+/// fetcher - The fetcher used to get the product pages
+/// cache - The details of the product pages fetched in previous runs
+/// concurrency - Maximum number of product pages fetched at the same time
+///
+/// # Return
+///
+/// The enriched releases and the details of their product pages to cache for the next run
+pub async fn enrich_releases(
+    releases: Vec<UpcomingRelease>,
+    fetcher: &Fetcher,
+    cache: &[CachedDetails],
+    concurrency: usize,
+) -> (Vec<UpcomingRelease>, Vec<CachedDetails>) {
+    log::trace!("details::enrich_releases()");
+
+    // "buffered" keeps the releases in their order
+    let results: Vec<(UpcomingRelease, Option<CachedDetails>)> = futures::stream::iter(releases)
+        .map(|release| enrich_release(release, fetcher, cache))
+        .buffered(concurrency.max(1))
+        .collect()
+        .await;
+
+    let mut enriched = Vec::new();
+    let mut next_cache = Vec::new();
+
+    for (release, cached) in results {
+        enriched.push(release);
+        next_cache.extend(cached);
+    }
+
+    log::info!("Added the details of {} product page(s)", &next_cache.len());
+
+    (enriched, next_cache)
+}
+
+/// Add the details of its product page to a release.
+///
+/// # Arguments
+///
+/// release - The upcoming release
+/// fetcher - The fetcher used to get the product page
+/// cache - The details of the product pages fetched in previous runs
+///
+/// # Return
+///
+/// The enriched release and the details of its product page. None if it has no product page or
+/// the product page could not be fetched
+async fn enrich_release(
+    mut release: UpcomingRelease,
+    fetcher: &Fetcher,
+    cache: &[CachedDetails],
+) -> (UpcomingRelease, Option<CachedDetails>) {
+    log::trace!("details::enrich_release()");
+
+    if release.url.is_empty() {
+        return (release, None);
+    }
+
+    let cached = cache.iter().find(|cached| {
+        cached.url == release.url
+            && cached.title == release.title
+            && cached.date == release.date
+            && cached.price == release.price
+    });
+
+    let details = match cached {
+        Some(cached) => {
+            log::debug!("Reusing cached details of '{}'", &release.url);
+            cached.details.clone()
+        }
+        None => match fetcher.fetch(&release.url).await {
+            Ok(html_content) => parse_product_page(&html_content),
+            Err(err) => {
+                log::warn!(
+                    "Failed to get the product page of '{}': {:#}",
+                    &release.title,
+                    err
+                );
+                return (release, None);
+            }
+        },
+    };
+
+    // the search result is cached as it was found, before the details are added
+    let cached = CachedDetails {
+        url: release.url.clone(),
+        title: release.title.clone(),
+        date: release.date,
+        price: release.price.clone(),
+        details: details.clone(),
+    };
+
+    apply_details(&mut release, &details);
+
+    (release, Some(cached))
+}
+
+/// Add the details found on the product page to a release. Details not found keep the values of
+/// the search result.
+fn apply_details(release: &mut UpcomingRelease, details: &ProductDetails) {
+    if details.pages.is_some() {
+        release.pages = details.pages;
+    }
+    if !details.series.is_empty() {
+        release.series = details.series.clone();
+    }
+    if details.volume.is_some() {
+        release.volume = details.volume;
+    }
+    if !details.translator.is_empty() {
+        release.translator = details.translator.clone();
+    }
+    if !details.original_title.is_empty() {
+        release.original_title = details.original_title.clone();
+    }
+    if !details.description.is_empty() {
+        release.description = details.description.clone();
+    }
+
+    // the product page knows the exact publication date
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(&details.date, "%Y-%m-%d") {
+        let date = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+        if date != release.date {
+            log::info!(
+                "Release date of '{}' corrected from {} to {}",
+                &release.title,
+                release.date.format("%Y-%m-%d"),
+                date.format("%Y-%m-%d")
+            );
+            release.date = date;
+        }
+    }
+}
+
+/// Get the value of the first label found. The value either follows the label and a colon in the
+/// same line, or it is the next line.
+fn label_value(lines: &[&str], labels: &[&str]) -> Option<String> {
+    for (index, line) in lines.iter().enumerate() {
+        let lowercase_line = line.to_lowercase();
+
+        for label in labels {
+            let label = format!("{}:", label.to_lowercase());
+
+            if lowercase_line == label || lowercase_line == label.trim_end_matches(':') {
+                if let Some(next_line) = lines.get(index + 1) {
+                    return Some(next_line.trim_start_matches(':').trim().to_string());
+                }
+            } else if lowercase_line.starts_with(&label) {
+                let value = line.get(label.len()..).unwrap_or_default().trim();
+                if !value.is_empty() {
+                    return Some(value.to_string());
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Get the first number of a value, e.g. 384 of "384 Seiten".
+fn first_number(value: &str) -> Option<u32> {
+    value
+        .split(|c: char| !c.is_ascii_digit())
+        .find(|number| !number.is_empty())?
+        .parse()
+        .ok()
+}
+
+/// Get the first German date (DD.MM.YYYY) of a value.
+fn german_date(value: &str) -> Option<chrono::NaiveDate> {
+    let re = regex::Regex::new(r"\d{1,2}\.\d{1,2}\.\d{4}").unwrap();
+    let date = re.find(value)?.as_str();
+
+    chrono::NaiveDate::parse_from_str(date, "%d.%m.%Y").ok()
+}
+
+/// Get the description blurb of a product page. Every sequence of whitespaces is reduced to a
+/// single space.
+fn description(document: &scraper::Html) -> Option<String> {
+    DESCRIPTION.iter().find_map(|selector| {
+        let selector = scraper::Selector::parse(selector).ok()?;
+        let element = document.select(&selector).next()?;
+
+        let text = match element.value().attr("content") {
+            Some(content) => content.to_string(),
+            None => element.text().collect::<Vec<_>>().join(" "),
+        };
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        if text.is_empty() {
+            None
+        } else {
+            Some(text)
+        }
+    })
+}
+
+/// Load the details of the product pages fetched in previous runs from the cache file.
+///
+/// # Arguments
+///
+/// path - Path to the cache file
+///
+/// # Return
+///
+/// Ok(Vec<CachedDetails>) - The cached details. Empty if there is no cache file yet
+/// Err(err) - Some error occured
+pub fn load_cache(path: &str) -> Result<Vec<CachedDetails>> {
+    log::trace!("details::load_cache()");
+
+    // no cache file means there was no previous run
+    if !std::path::Path::new(path).exists() {
+        log::info!(
+            "Details cache '{}' not found, all product pages are fetched",
+            path
+        );
+        return Ok(Vec::new());
+    }
+
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read details cache '{}'", path))?;
+    let cache: Vec<CachedDetails> = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse details cache '{}'", path))?;
+
+    log::debug!(
+        "Loaded {} cached product pages from '{}'",
+        cache.len(),
+        path
+    );
+
+    Ok(cache)
+}
+
+/// Save the details of the product pages of this run to the cache file.
+///
+/// # Arguments
+///
+/// path - Path to the cache file
+/// cache - The details to save
+///
+/// # Return
+///
+/// Ok() - Successfully saved the details
+/// Err(err) - Some error occured
+pub fn save_cache(path: &str, cache: &[CachedDetails]) -> Result<()> {
+    log::trace!("details::save_cache()");
+
+    if let Some(parent) = std::path::Path::new(path).parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory of details cache '{}'", path))?;
+    }
+
+    let json = serde_json::to_string_pretty(cache)
+        .with_context(|| "Failed to serialize cached product pages to JSON")?;
+    std::fs::write(path, json + "\n")
+        .with_context(|| format!("Failed to write details cache '{}'", path))?;
+
+    log::debug!("Saved {} cached product pages to '{}'", cache.len(), path);
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+//! This module groups the editions of the same work, e.g. in different formats, languages or found
+//! on different release sources.
+
+use crate::customtypes::{Edition, UpcomingRelease};
+use crate::format;
+
+/// Group the releases of the same work under one entry. Releases are the same work if they share
+/// the ISBN, or if author and (normalized) title match. A translation matches by the title of its
+/// original edition, which is only known from the product page (see `--details`). The earliest
+/// release is kept as entry, the others are listed as its editions. Releases identical to an
+/// edition already listed are dropped.
+///
+/// # Arguments
+///
+/// releases - The upcoming releases
+///
+/// # Return
+///
+/// The upcoming releases, each with its alternative editions
+pub fn group_editions(releases: Vec<UpcomingRelease>) -> Vec<UpcomingRelease> {
+    log::trace!("editions::group_editions()");
+
+    let mut grouped: Vec<UpcomingRelease> = Vec::new();
+
+    for mut release in releases {
+        let Some(entry) = grouped
+            .iter_mut()
+            .find(|entry| is_same_work(entry, &release))
+        else {
+            grouped.push(release);
+            continue;
+        };
+
+        let edition = edition(&release);
+        if is_duplicate(&self::edition(entry), &edition)
+            || entry
+                .editions
+                .iter()
+                .any(|listed| is_duplicate(listed, &edition))
+        {
+            log::debug!("Dropping duplicate '{}' ({})", &release.title, &release.url);
+            continue;
+        }
+
+        log::debug!(
+            "Grouping '{}' ({}, {}) with '{}'",
+            &release.title,
+            &release.format,
+            &release.source,
+            &entry.title
+        );
+
+        // the earliest edition is the entry of the work
+        if release.date < entry.date {
+            std::mem::swap(entry, &mut release);
+            entry.editions = std::mem::take(&mut release.editions);
+        }
+
+        // the original title still identifies the work for the releases to come
+        if entry.original_title.is_empty() {
+            entry.original_title = release.original_title.clone();
+        }
+        entry.editions.push(self::edition(&release));
+    }
+
+    grouped
+}
+
+/// Check whether a release is another edition of an already grouped work.
+fn is_same_work(entry: &UpcomingRelease, release: &UpcomingRelease) -> bool {
+    if entry.author != release.author {
+        return false;
+    }
+
+    let same_isbn = !release.isbn.is_empty()
+        && (entry.isbn == release.isbn
+            || entry
+                .editions
+                .iter()
+                .any(|edition| edition.isbn == release.isbn));
+
+    // the titles of the grouped editions and of the original edition identify the work as well
+    let work_titles: Vec<String> = std::iter::once(entry.title.as_str())
+        .chain(std::iter::once(entry.original_title.as_str()))
+        .chain(entry.editions.iter().map(|edition| edition.title.as_str()))
+        .filter(|title| !title.is_empty())
+        .map(format::normalize_title)
+        .collect();
+
+    same_isbn
+        || [&release.title, &release.original_title]
+            .iter()
+            .filter(|title| !title.is_empty())
+            .any(|title| work_titles.contains(&format::normalize_title(title)))
+}
+
+/// Check whether two editions are the same, i.e. they share the product page, or they are of the
+/// same format and language, found on the same release source and released on the same date.
+fn is_duplicate(listed: &Edition, edition: &Edition) -> bool {
+    (!edition.url.is_empty() && listed.url == edition.url)
+        || (listed.format == edition.format
+            && listed.language == edition.language
+            && listed.source == edition.source
+            && listed.date == edition.date)
+}
+
+/// Turn a release into an edition of another release.
+fn edition(release: &UpcomingRelease) -> Edition {
+    Edition {
+        title: release.title.clone(),
+        date: release.date,
+        language: release.language.clone(),
+        source: release.source.clone(),
+        url: release.url.clone(),
+        format: release.format.clone(),
+        isbn: release.isbn.clone(),
+    }
+}
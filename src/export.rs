@@ -0,0 +1,439 @@
+//! This module exports the upcoming releases to machine-readable formats.
+
+use crate::customtypes::{Locale, UpcomingRelease};
+use crate::reissues;
+use crate::series;
+use anyhow::{Context, Result};
+
+const CSV_HEADER: [&str; 18] = [
+    "author",
+    "title",
+    "date",
+    "source",
+    "url",
+    "language",
+    "editions",
+    "format",
+    "reissue",
+    "isbn",
+    "publisher",
+    "price",
+    "cover_url",
+    "series",
+    "volume",
+    "pages",
+    "translator",
+    "description",
+];
+
+/// Custom data type of the labels of the details in the description of a calendar event.
+struct IcsLabels {
+    format: &'static str,
+    publisher: &'static str,
+    price: &'static str,
+    isbn: &'static str,
+    series: &'static str,
+    pages: &'static str,
+    translator: &'static str,
+    source: &'static str,
+    language: &'static str,
+    editions: &'static str,
+    reissue: &'static str,
+}
+
+/// Version of the JSON schema. It has to be increased with every incompatible change of the
+/// exported fields. Version 2 records the detected book format (e.g. "Hardcover" instead of "Buch")
+/// and the series and volume apart from the title.
+pub const SCHEMA_VERSION: u32 = 2;
+
+const ICS_PRODUCT_ID: &str = "-//upcoming_book_releases//Upcoming Book Releases//DE";
+const ICS_UID_DOMAIN: &str = "upcoming-book-releases";
+const ICS_MAX_LINE_LENGTH: usize = 75;
+
+/// Custom data type of the JSON document.
+#[derive(serde::Serialize)]
+struct JsonDocument<'a> {
+    schema_version: u32,
+    releases: &'a [UpcomingRelease],
+}
+
+/// Custom data type of a single NDJSON line.
+#[derive(serde::Serialize)]
+struct JsonLine<'a> {
+    schema_version: u32,
+    #[serde(flatten)]
+    release: &'a UpcomingRelease,
+}
+
+/// Export the releases as comma-separated values according to RFC 4180. The first row contains
+/// the column names, the date is given as ISO 8601 date (YYYY-MM-DD). The editions in other
+/// languages are listed in a single column, e.g. "eng 2024-09-30; fra 2024-11-02".
+///
+/// # Arguments
+///
+/// releases – The upcoming releases
+///
+/// # Return
+///
+/// The releases as CSV
+pub fn to_csv(releases: &[UpcomingRelease]) -> String {
+    log::trace!("export::to_csv()");
+
+    let mut rows = vec![csv_row(&CSV_HEADER)];
+
+    for release in releases {
+        let date = release.date.format("%Y-%m-%d").to_string();
+        let volume = release.volume.map(|volume| volume.to_string());
+        let pages = release.pages.map(|pages| pages.to_string());
+
+        rows.push(csv_row(&[
+            &release.author,
+            &release.title,
+            &date,
+            &release.source,
+            &release.url,
+            &release.language,
+            &editions_summary(release),
+            &release.format,
+            &release.reissue,
+            &release.isbn,
+            &release.publisher,
+            &release.price,
+            &release.cover_url,
+            &release.series,
+            volume.as_deref().unwrap_or_default(),
+            pages.as_deref().unwrap_or_default(),
+            &release.translator,
+            &release.description,
+        ]));
+    }
+
+    // RFC 4180 demands CRLF as line break, also after the last row
+    rows.iter().map(|row| row.to_owned() + "\r\n").collect()
+}
+
+/// Summarize the further editions of a release, e.g. "eng Taschenbuch 2024-09-30; fre 2024-11-02".
+/// Unknown languages and formats are left out.
+fn editions_summary(release: &UpcomingRelease) -> String {
+    release
+        .editions
+        .iter()
+        .map(|edition| {
+            let date = edition.date.format("%Y-%m-%d").to_string();
+            [edition.language.as_str(), edition.format.as_str(), &date]
+                .into_iter()
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Join the fields to a CSV row.
+fn csv_row(fields: &[&str]) -> String {
+    fields
+        .iter()
+        .map(|field| csv_field(field))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Quote a CSV field if it contains a comma, a double quote or a line break. Double quotes inside
+/// the field are escaped by another double quote.
+///
+/// # Arguments
+///
+/// field - The field to quote
+///
+/// # Return
+///
+/// The (quoted) field
+pub fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Export the releases as a single JSON document. The document contains the schema version and
+/// the list of releases.
+///
+/// # Arguments
+///
+/// releases – The upcoming releases
+///
+/// # Return
+///
+/// Ok(String) - The releases as JSON
+/// Err(err) - Some error occured
+pub fn to_json(releases: &[UpcomingRelease]) -> Result<String> {
+    log::trace!("export::to_json()");
+
+    let document = JsonDocument {
+        schema_version: SCHEMA_VERSION,
+        releases,
+    };
+
+    let json = serde_json::to_string_pretty(&document)
+        .with_context(|| "Failed to serialize releases to JSON")?;
+
+    Ok(json + "\n")
+}
+
+/// Export the releases as newline-delimited JSON. Every line is a JSON object of a single release,
+/// including the schema version.
+///
+/// # Arguments
+///
+/// releases – The upcoming releases
+///
+/// # Return
+///
+/// Ok(String) - The releases as NDJSON
+/// Err(err) - Some error occured
+pub fn to_ndjson(releases: &[UpcomingRelease]) -> Result<String> {
+    log::trace!("export::to_ndjson()");
+
+    let mut ndjson = String::new();
+
+    for release in releases {
+        let line = JsonLine {
+            schema_version: SCHEMA_VERSION,
+            release,
+        };
+
+        ndjson += &serde_json::to_string(&line)
+            .with_context(|| format!("Failed to serialize release {:?} to JSON", release))?;
+        ndjson += "\n";
+    }
+
+    Ok(ndjson)
+}
+
+/// Export the releases as iCalendar (RFC 5545) with one all-day event per release. The UID of an
+/// event is derived from author, title and source, so importing the calendar again updates the
+/// events instead of duplicating them.
+///
+/// # Arguments
+///
+/// releases – The upcoming releases
+/// locale - The language of the labels in the event descriptions
+///
+/// # Return
+///
+/// The releases as iCalendar
+pub fn to_ics(releases: &[UpcomingRelease], locale: Locale) -> String {
+    log::trace!("export::to_ics()");
+
+    let labels = ics_labels(locale);
+
+    let timestamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", ICS_PRODUCT_ID),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+    ];
+
+    for release in releases {
+        // an all-day event ends (exclusively) on the next day
+        let start = release.date.date_naive();
+        let end = start.succ_opt().unwrap_or(start);
+
+        let mut description = Vec::new();
+        if !release.format.is_empty() {
+            description.push(format!("{}: {}", labels.format, release.format));
+        }
+        if !release.publisher.is_empty() {
+            description.push(format!("{}: {}", labels.publisher, release.publisher));
+        }
+        if !release.price.is_empty() {
+            description.push(format!("{}: {}", labels.price, release.price));
+        }
+        if !release.isbn.is_empty() {
+            description.push(format!("{}: {}", labels.isbn, release.isbn));
+        }
+        if !release.series.is_empty() {
+            match release.volume {
+                Some(volume) => description.push(format!(
+                    "{}: {} {}",
+                    labels.series,
+                    release.series,
+                    series::volume_label(volume)
+                )),
+                None => description.push(format!("{}: {}", labels.series, release.series)),
+            }
+        }
+        if let Some(pages) = release.pages {
+            description.push(format!("{}: {}", labels.pages, pages));
+        }
+        if !release.translator.is_empty() {
+            description.push(format!("{}: {}", labels.translator, release.translator));
+        }
+        if !release.source.is_empty() {
+            description.push(format!("{}: {}", labels.source, release.source));
+        }
+        if !release.language.is_empty() {
+            description.push(format!("{}: {}", labels.language, release.language));
+        }
+        if !release.editions.is_empty() {
+            description.push(format!(
+                "{}: {}",
+                labels.editions,
+                editions_summary(release)
+            ));
+        }
+        if !release.reissue.is_empty() {
+            description.push(format!(
+                "{}: {}",
+                labels.reissue,
+                reissues::describe_reason(&release.reissue, locale)
+            ));
+        }
+        // the blurb is set apart from the details by a blank line
+        if !release.description.is_empty() {
+            description.push(format!("\n{}", release.description));
+        }
+
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}@{}", ics_uid(release), ICS_UID_DOMAIN));
+        lines.push(format!("DTSTAMP:{}", timestamp));
+        lines.push(format!("DTSTART;VALUE=DATE:{}", start.format("%Y%m%d")));
+        lines.push(format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")));
+        lines.push(format!(
+            "SUMMARY:{}",
+            ics_text(&format!("{} - {}", release.author, release.title))
+        ));
+        if !description.is_empty() {
+            lines.push(format!("DESCRIPTION:{}", ics_text(&description.join("\n"))));
+        }
+        if !release.url.is_empty() {
+            lines.push(format!("URL:{}", release.url));
+        }
+        // the cover is attached as image of the event (RFC 7986)
+        if !release.cover_url.is_empty() {
+            lines.push(format!(
+                "IMAGE;VALUE=URI;DISPLAY=THUMBNAIL:{}",
+                release.cover_url
+            ));
+        }
+        lines.push("TRANSP:TRANSPARENT".to_string());
+        lines.push("END:VEVENT".to_string());
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    // RFC 5545 demands CRLF as line break and long lines to be folded
+    lines.iter().map(|line| ics_fold(line) + "\r\n").collect()
+}
+
+/// Get the labels of the details in the description of a calendar event.
+fn ics_labels(locale: Locale) -> IcsLabels {
+    match locale {
+        Locale::De => IcsLabels {
+            format: "Format",
+            publisher: "Verlag",
+            price: "Preis",
+            isbn: "ISBN",
+            series: "Reihe",
+            pages: "Seiten",
+            translator: "Übersetzung",
+            source: "Quelle",
+            language: "Sprache",
+            editions: "Ausgaben",
+            reissue: "Neuauflage",
+        },
+        Locale::En => IcsLabels {
+            format: "Format",
+            publisher: "Publisher",
+            price: "Price",
+            isbn: "ISBN",
+            series: "Series",
+            pages: "Pages",
+            translator: "Translator",
+            source: "Source",
+            language: "Language",
+            editions: "Editions",
+            reissue: "Reissue",
+        },
+        Locale::Fr => IcsLabels {
+            format: "Format",
+            publisher: "Éditeur",
+            price: "Prix",
+            isbn: "ISBN",
+            series: "Série",
+            pages: "Pages",
+            translator: "Traduction",
+            source: "Source",
+            language: "Langue",
+            editions: "Éditions",
+            reissue: "Réédition",
+        },
+    }
+}
+
+/// Derive a stable UID from author, title and source of a release. The 64-bit FNV-1a hash is used
+/// because, unlike the hasher of the standard library, it never changes between Rust versions.
+///
+/// # Arguments
+///
+/// release - The release to derive the UID from
+///
+/// # Return
+///
+/// The UID as hexadecimal string
+pub fn ics_uid(release: &UpcomingRelease) -> String {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    // separate the fields by a unit separator, so "ab" + "c" differs from "a" + "bc"
+    let key = [&release.author, &release.title, &release.source]
+        .map(|field| field.as_str())
+        .join("\u{1f}");
+
+    let hash = key.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    });
+
+    format!("{:016x}", hash)
+}
+
+/// Escape backslashes, semicolons, commas and line breaks of an iCalendar text value.
+fn ics_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Fold a content line longer than 75 octets into several lines. Every continuation line starts
+/// with a space. Multi-byte characters are never split.
+///
+/// # Arguments
+///
+/// line - The content line to fold
+///
+/// # Return
+///
+/// The folded line, separated by CRLF
+pub fn ics_fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut line_length = 0;
+
+    for c in line.chars() {
+        if line_length + c.len_utf8() > ICS_MAX_LINE_LENGTH {
+            folded += "\r\n ";
+            // the leading space counts to the length of the continuation line
+            line_length = 1;
+        }
+        folded.push(c);
+        line_length += c.len_utf8();
+    }
+
+    folded
+}
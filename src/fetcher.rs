@@ -0,0 +1,228 @@
+//! This module fetches the HTML contents of a webpage, either via HTTP or from a directory of saved
+//! HTML pages.
+
+use crate::ratelimit::RateLimiter;
+use anyhow::{Context, Result};
+use rand::Rng;
+
+/// Custom data type to configure how often and how long to wait before a failed request is
+/// retried. The delay doubles with every retry, up to a maximum, and is randomized (jitter) so
+/// concurrent requests do not retry at the same time.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub retries: u32,
+    pub base_delay: std::time::Duration,
+    pub max_delay: std::time::Duration,
+}
+
+impl RetryPolicy {
+    pub fn create(retries: u32, base_delay: std::time::Duration) -> Self {
+        RetryPolicy {
+            retries,
+            base_delay,
+            max_delay: base_delay * 32,
+        }
+    }
+
+    /// Get the delay before the given retry. The exponential delay is capped at the maximum delay
+    /// and randomized between half and the full delay.
+    ///
+    /// # Arguments
+    ///
+    /// retry - The number of the retry, starting at 0
+    ///
+    /// # Return
+    ///
+    /// The delay to wait before the retry
+    pub fn delay(&self, retry: u32) -> std::time::Duration {
+        let exponential = self
+            .base_delay
+            .saturating_mul(2_u32.saturating_pow(retry))
+            .min(self.max_delay);
+
+        exponential.mul_f64(rand::thread_rng().gen_range(0.5..=1.0))
+    }
+}
+
+/// Custom data type to get the HTML contents of an URL.
+pub enum Fetcher {
+    /// Send HTTP GET requests to the webpage, limited by the rate limiter and retried on transient
+    /// failures
+    Http {
+        client: reqwest::Client,
+        rate_limiter: RateLimiter,
+        retry_policy: RetryPolicy,
+    },
+    /// Read saved HTML pages from a directory instead of sending requests
+    Offline(std::path::PathBuf),
+}
+
+impl Fetcher {
+    /// Create a fetcher sending HTTP GET requests to the webpages.
+    ///
+    /// # Arguments
+    ///
+    /// timeout - Maximum time a single request may take
+    /// rate_limiter - The rate limiter for the requests
+    /// retry_policy - How often a failed request is retried
+    ///
+    /// # Return
+    ///
+    /// Ok(Fetcher) - The HTTP fetcher
+    /// Err(err) - Some error occured
+    pub fn http(
+        timeout: std::time::Duration,
+        rate_limiter: RateLimiter,
+        retry_policy: RetryPolicy,
+    ) -> Result<Self> {
+        log::trace!("fetcher::http()");
+
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .connect_timeout(timeout)
+            .build()
+            .with_context(|| "Failed to create HTTP client")?;
+
+        Ok(Fetcher::Http {
+            client,
+            rate_limiter,
+            retry_policy,
+        })
+    }
+
+    /// Get the HTML content of an URL.
+    ///
+    /// # Arguments
+    ///
+    /// url - The URL to get the HTML content from
+    ///
+    /// # Return
+    ///
+    /// Ok(String) - The HTML content
+    /// Err(err) - Some error occured
+    pub async fn fetch(&self, url: &str) -> Result<String> {
+        log::trace!("fetcher::fetch()");
+        log::debug!("URL to check: '{}'", url);
+
+        match self {
+            Fetcher::Http {
+                client,
+                rate_limiter,
+                retry_policy,
+            } => {
+                let mut retry = 0;
+
+                loop {
+                    // be polite and do not flood the webpage with requests
+                    rate_limiter.acquire(url).await;
+
+                    match request(client, url).await {
+                        Ok(html_content) => return Ok(html_content),
+                        Err((err, transient)) if transient && retry < retry_policy.retries => {
+                            let delay = retry_policy.delay(retry);
+                            retry += 1;
+
+                            log::warn!(
+                                "{:#}. Retry {}/{} in {:?}",
+                                err,
+                                retry,
+                                retry_policy.retries,
+                                delay
+                            );
+                            tokio::time::sleep(delay).await;
+                        }
+                        Err((err, _)) => return Err(err),
+                    }
+                }
+            }
+            Fetcher::Offline(directory) => {
+                let path = directory.join(offline_file_name(url));
+                log::debug!("Reading saved HTML page '{}'", path.display());
+
+                std::fs::read_to_string(&path).with_context(|| {
+                    format!(
+                        "Failed to read saved HTML page '{}' for '{}'",
+                        path.display(),
+                        url
+                    )
+                })
+            }
+        }
+    }
+}
+
+/// Send a single HTTP GET request.
+///
+/// # Arguments
+///
+/// client - The HTTP client used to send the request
+/// url - The URL to get the HTML content from
+///
+/// # Return
+///
+/// Ok(String) - The HTML content
+/// Err((err, transient)) - Some error occured. "transient" tells whether a retry could succeed
+async fn request(client: &reqwest::Client, url: &str) -> Result<String, (anyhow::Error, bool)> {
+    // Send a GET request to the URL and retrieve the response. Timeouts and connection errors
+    // are worth a retry
+    let response = client.get(url).send().await.map_err(|err| {
+        let transient = err.is_timeout() || err.is_connect() || err.is_request();
+        (
+            anyhow::Error::new(err)
+                .context(format!("Failed to send HTTP GET request to '{}'", url)),
+            transient,
+        )
+    })?;
+
+    // Check if the request was successful. Server errors and "Too Many Requests" are worth a
+    // retry, other client errors are not
+    let status = response.status();
+    if !status.is_success() {
+        let transient =
+            status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS;
+        return Err((
+            anyhow::anyhow!("Request to '{}' failed with status code: {:?}", url, status),
+            transient,
+        ));
+    }
+
+    // Read the response body as a string
+    response.text().await.map_err(|err| {
+        let transient = err.is_timeout();
+        (
+            anyhow::Error::new(err).context("Failed to get HTML content"),
+            transient,
+        )
+    })
+}
+
+/// Map an URL to the file name its HTML page is saved under in offline mode. The scheme is
+/// dropped and every character except ASCII letters, digits, '-' and '.' is replaced by '_'.
+/// Example: "https://www.weltbild.de/suche/King+Stephen?jahr=0" is mapped to
+/// "www.weltbild.de_suche_King_Stephen_jahr_0.html"
+///
+/// # Arguments
+///
+/// url - The URL to map
+///
+/// # Return
+///
+/// The file name of the saved HTML page
+pub fn offline_file_name(url: &str) -> String {
+    log::trace!("fetcher::offline_file_name()");
+
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+
+    let file_name: String = without_scheme
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    file_name + ".html"
+}
@@ -1,7 +1,9 @@
 //! This module provides functions to format author name, title and release date for an upcoming
 //! release.
 
+use crate::customtypes::MediaFormat;
 use anyhow::Result;
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
 
 /// Rearrange the author name from "<surname, firstname>" to "<firstname surname>".
 /// Example: "Brown, Dan" is rearranged to "Dan Brown"
@@ -27,6 +29,54 @@ pub fn format_author_name(author: &str) -> Result<String> {
     anyhow::bail!("Failed to rearrange author name for: '{}'", author);
 }
 
+/// Split the contributors of a release, as listed on the release source, into single names. The
+/// names can be separated by ',', ';', '/', '&', "und" or "and". Roles in brackets like
+/// "(Autor)" are removed.
+/// Example: "Douglas Preston (Autor), Lincoln Child" is split into "Douglas Preston" and
+/// "Lincoln Child"
+///
+/// # Arguments
+///
+/// contributors - The contributors, listed as <forename surname>
+///
+/// # Return
+///
+/// The single names of the contributors. Could be empty if no name is given
+pub fn split_contributors(contributors: &str) -> Vec<String> {
+    log::trace!("format::split_contributors()");
+
+    let roles = regex::Regex::new(r"\([^)]*\)").unwrap();
+    let separators = regex::Regex::new(r"[,;/&]|\s+(?:und|and)\s+").unwrap();
+
+    separators
+        .split(&roles.replace_all(contributors, ""))
+        .map(|name| name.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Check whether an author is one of the contributors of a release. Case and whitespaces are
+/// ignored.
+///
+/// # Arguments
+///
+/// contributors - The single names of the contributors
+/// author - The author, listed as <forename surname>
+///
+/// # Return
+///
+/// true if the author is one of the contributors, otherwise false
+pub fn is_contributor(contributors: &[String], author: &str) -> bool {
+    log::trace!("format::is_contributor()");
+
+    let normalized_author = normalize_title(author);
+
+    !normalized_author.is_empty()
+        && contributors
+            .iter()
+            .any(|contributor| normalize_title(contributor) == normalized_author)
+}
+
 /// Parse the title of the upcoming release from an HTML content.
 ///
 /// # Arguments
@@ -97,3 +147,141 @@ pub fn format_release_date(html_content: &str) -> Result<chrono::DateTime<chrono
         anyhow::bail!("Date substring not found");
     }
 }
+
+/// Detect the book format of a release from the format description of the release source. Case is
+/// ignored, audio books and e-books are checked first, because their descriptions often contain
+/// "Buch" as well. Paperbacks are checked before the remaining books for the same reason.
+/// Example: "Buch (Gebunden)" is detected as hardcover, "Buch (Kartoniert)" as paperback and
+/// "Hörbuch-Download (MP3)" as audio book to download
+///
+/// # Arguments
+///
+/// description - The format description, e.g. "Taschenbuch"
+///
+/// # Return
+///
+/// Some(MediaFormat) - The detected book format
+/// None - No known book format is found
+pub fn detect_media_format(description: &str) -> Option<MediaFormat> {
+    log::trace!("format::detect_media_format()");
+
+    let description = description.to_lowercase();
+    let contains_any =
+        |patterns: &[&str]| patterns.iter().any(|pattern| description.contains(pattern));
+
+    if contains_any(&["hörbuch-download", "hörbuch download", "mp3-download"]) {
+        Some(MediaFormat::HoerbuchDownload)
+    } else if contains_any(&["hörbuch", "audio-cd", "mp3-cd"]) {
+        Some(MediaFormat::Hoerbuch)
+    } else if contains_any(&["ebook", "e-book"]) {
+        Some(MediaFormat::EBook)
+    } else if contains_any(&["taschenbuch", "kartoniert", "broschiert", "paperback"]) {
+        Some(MediaFormat::Taschenbuch)
+    } else if contains_any(&["buch", "gebunden", "hardcover"]) {
+        Some(MediaFormat::Hardcover)
+    } else {
+        None
+    }
+}
+
+/// Normalize a title to compare it with other titles. The title is lowercased and every sequence of
+/// whitespaces is reduced to a single space.
+///
+/// # Arguments
+///
+/// title - The title to normalize
+///
+/// # Return
+///
+/// The normalized title
+pub fn normalize_title(title: &str) -> String {
+    log::trace!("format::normalize_title()");
+
+    title
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Normalize an author name to detect duplicates. The name is lowercased, diacritics are removed
+/// and every sequence of whitespaces is reduced to a single space.
+/// Example: "Lagerlöf, Selma" and "lagerlof,  selma" are both normalized to "lagerlof, selma"
+///
+/// # Arguments
+///
+/// author - The author name to normalize
+///
+/// # Return
+///
+/// The normalized author name
+pub fn normalize_author_name(author: &str) -> String {
+    log::trace!("format::normalize_author_name()");
+
+    author
+        .nfd()
+        .filter(|&c| !is_combining_mark(c))
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Get the language code of a language node of Weltbild, which is its last path segment.
+/// Example: "/language/eng" is turned into "eng"
+///
+/// # Arguments
+///
+/// language - The language node, empty for all languages
+///
+/// # Return
+///
+/// The language code. Empty if no language is given
+pub fn language_code(language: &str) -> String {
+    log::trace!("format::language_code()");
+
+    language
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Find the first valid ISBN-13 in a text. Hyphens inside the ISBN are ignored, the check digit
+/// has to match.
+/// Example: "ISBN: 978-3-453-27363-4" is turned into "9783453273634"
+///
+/// # Arguments
+///
+/// text - The text to search, e.g. the text of a search result
+///
+/// # Return
+///
+/// Some(String) - The ISBN-13 without hyphens
+/// None - No valid ISBN-13 is found
+pub fn find_isbn(text: &str) -> Option<String> {
+    log::trace!("format::find_isbn()");
+
+    text.split(|c: char| !c.is_ascii_digit() && c != '-')
+        .map(|candidate| candidate.replace('-', ""))
+        .find(|candidate| {
+            candidate.len() == 13
+                && (candidate.starts_with("978") || candidate.starts_with("979"))
+                && is_valid_isbn(candidate)
+        })
+}
+
+/// Check the check digit of an ISBN-13. The digits are weighted alternately with 1 and 3, their sum
+/// has to be a multiple of 10.
+fn is_valid_isbn(isbn: &str) -> bool {
+    let sum: u32 = isbn
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .enumerate()
+        .map(|(index, digit)| if index % 2 == 0 { digit } else { digit * 3 })
+        .sum();
+
+    sum.is_multiple_of(10)
+}
@@ -1,45 +1,61 @@
 mod authors;
 mod cli;
+mod config;
 mod customtypes;
+mod details;
+mod editions;
+mod export;
+mod fetcher;
 mod format;
 mod logger;
+mod ratelimit;
+mod reissues;
 mod releases;
 mod scraper;
+mod series;
+mod sources;
+mod state;
 mod tests;
 
 use anyhow::{Context, Result};
-use clap::Parser;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // Capture the current time before executing the program
     let start_time = std::time::Instant::now();
 
-    let args = cli::Args::parse();
+    let args = cli::Args::parse_args();
+
+    // read the config file, overridden by the command line arguments
+    let config = config::create_config(&args).with_context(|| "Failed to set up configuration")?;
 
     // first of all, set up the logger
-    logger::init_logger(&args.loglevel)
-        .with_context(|| format!("Failed to set loglevel '{}'", &args.loglevel))?;
+    logger::init_logger(&config.loglevel)
+        .with_context(|| format!("Failed to set loglevel '{}'", &config.loglevel))?;
 
     log::debug!("{args:?}");
+    log::debug!("{config:?}");
 
-    // get the authors
-    let authors = authors::read_authors(&args.authors_file)
-        .with_context(|| format!("Failed to extract authors from '{}'", &args.authors_file))?;
-
-    // parse the HTML contents to get the potential upcoming releases
-    let upcoming_releases = scraper::parse_contents(authors)
-        .await
-        .with_context(|| "Failed to scrape the HTML contents from Webpage")?;
-
-    // Create releases file
-    releases::create_releases(upcoming_releases, &args.dest_release, &args.release_file)
-        .with_context(|| {
-            format!(
-                "Failed to create release file '{}', located in '{}'",
-                &args.release_file, &args.dest_release
-            )
-        })?;
+    match &args.command {
+        Some(cli::Command::Validate { fix }) => {
+            authors::validate_authors(&config.authors_file, *fix).with_context(|| {
+                format!("Failed to validate authors file '{}'", &config.authors_file)
+            })?
+        }
+        Some(cli::Command::Authors { command }) => manage_authors(&config, command)?,
+        Some(cli::Command::Show) => {
+            let releases = releases::read_releases(&config.dest_release, &config.release_file)
+                .with_context(|| {
+                    format!(
+                        "Failed to show release file '{}', located in '{}'",
+                        &config.release_file, &config.dest_release
+                    )
+                })?;
+            print!("{}", releases);
+        }
+        Some(cli::Command::Fetch(fetch_args)) => fetch(&config, fetch_args).await?,
+        None => fetch(&config, &args.fetch).await?,
+    }
 
     // Capture the current time after executing the program
     let end_time = std::time::Instant::now();
@@ -51,3 +67,227 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+/// Search for the upcoming releases of all authors and write them into the release file.
+///
+/// # Arguments
+///
+/// config - The configuration of this run
+/// fetch_args - The arguments of searching for upcoming releases
+///
+/// # Return
+///
+/// Ok() - Successfully created the release file
+/// Err(err) - Some error occured
+async fn fetch(config: &config::Config, fetch_args: &cli::FetchArgs) -> Result<()> {
+    log::trace!("main::fetch()");
+
+    // get the authors
+    let authors = authors::read_authors(&config.authors_file)
+        .with_context(|| format!("Failed to extract authors from '{}'", &config.authors_file))?;
+
+    // set up the chain of release sources, releases before today are no upcoming releases
+    let today = chrono::Local::now().date_naive();
+    let release_sources = sources::create_sources(&fetch_args.sources, &config.search, today)
+        .with_context(|| format!("Failed to set up release sources {:?}", &fetch_args.sources))?;
+
+    // either read saved HTML pages or send requests to the webpages
+    let fetcher = match &fetch_args.offline_dir {
+        Some(offline_dir) => fetcher::Fetcher::Offline(offline_dir.into()),
+        None => fetcher::Fetcher::http(
+            std::time::Duration::from_secs(fetch_args.timeout),
+            ratelimit::RateLimiter::create(fetch_args.requests_per_second, 1),
+            fetcher::RetryPolicy::create(
+                fetch_args.retries,
+                std::time::Duration::from_millis(fetch_args.retry_delay),
+            ),
+        )?,
+    };
+
+    // parse the HTML contents to get the potential upcoming releases
+    let author_count = authors.len();
+    let scrape_result =
+        scraper::parse_contents(authors, &release_sources, &fetcher, fetch_args.concurrency)
+            .await
+            .with_context(|| "Failed to scrape the HTML contents from Webpage")?;
+
+    // summarize the authors which could not be checked
+    if !scrape_result.failed_authors.is_empty() {
+        log::warn!(
+            "{} author(s) could not be checked:",
+            scrape_result.failed_authors.len()
+        );
+        for (author, reason) in &scrape_result.failed_authors {
+            log::warn!("'{}': {}", author.name, reason);
+        }
+    }
+
+    // nothing was checked at all, so the release file, the state and the history are kept
+    if scrape_result.failed_authors.len() == author_count {
+        anyhow::bail!(
+            "None of the {} author(s) could be checked, release file left unchanged",
+            author_count
+        );
+    }
+
+    let mut found_releases = scrape_result.releases;
+    let mut next_history = None;
+
+    // titles already published in a previous run are reissues now
+    if let Some(history_file) = &fetch_args.history_file {
+        let history = reissues::load_history(history_file)
+            .with_context(|| format!("Failed to load history file '{}'", history_file))?;
+
+        reissues::mark_known_titles(&mut found_releases, &history, today);
+        next_history = Some(reissues::update_history(&history, &found_releases));
+    }
+
+    if fetch_args.reissues == customtypes::ReissueFilter::Hide {
+        let found = found_releases.len();
+        found_releases.retain(|release| release.reissue.is_empty());
+        log::info!(
+            "{} reissue(s) left out of the release file",
+            found - found_releases.len()
+        );
+    }
+
+    // add the details of the product pages, reusing the details of unchanged search results
+    let mut next_details_cache = None;
+    if fetch_args.details {
+        let cache = match &fetch_args.details_cache {
+            Some(details_cache) => details::load_cache(details_cache)
+                .with_context(|| format!("Failed to load details cache '{}'", details_cache))?,
+            None => Vec::new(),
+        };
+
+        let (enriched, next_cache) =
+            details::enrich_releases(found_releases, &fetcher, &cache, fetch_args.concurrency)
+                .await;
+        found_releases = enriched;
+        next_details_cache = Some(next_cache);
+    }
+
+    // list the same work in different formats, languages or from different sources only once
+    let mut upcoming_releases = editions::group_editions(found_releases);
+    let mut next_state = None;
+
+    // compare with the previous run to report new and changed releases
+    if let Some(state_file) = &fetch_args.state_file {
+        let previous = state::load_state(state_file)
+            .with_context(|| format!("Failed to load state file '{}'", state_file))?;
+
+        // the releases of authors which could not be checked did not disappear
+        let unchecked_authors: Vec<String> = scrape_result
+            .failed_authors
+            .iter()
+            .map(|(author, _)| {
+                format::format_author_name(author.release_author())
+                    .unwrap_or(author.release_author().to_string())
+            })
+            .collect();
+
+        let changes = state::compare(&previous, &upcoming_releases, &unchecked_authors, today);
+
+        // the report would mix with the releases on stdout, so it is logged instead
+        if config.dest_release == releases::STDOUT {
+            for line in state::render_report(&changes)?.lines() {
+                log::info!("{}", line);
+            }
+        } else {
+            std::fs::create_dir_all(&config.dest_release).with_context(|| {
+                format!(
+                    "Failed to create destination dir '{}'",
+                    &config.dest_release
+                )
+            })?;
+            let report_path = config.dest_release.clone() + "/" + &config.release_file + ".changes";
+            state::create_report(&changes, &report_path)
+                .with_context(|| format!("Failed to create report file '{}'", report_path))?;
+        }
+
+        next_state = Some(state::next_state(
+            &previous,
+            &upcoming_releases,
+            &unchecked_authors,
+            today,
+        ));
+
+        // only keep the new and changed releases
+        if fetch_args.only_changes {
+            upcoming_releases = changes
+                .into_iter()
+                .filter(|change| change.kind != customtypes::ChangeKind::Disappeared)
+                .map(|change| change.release)
+                .collect();
+        }
+    }
+
+    // Create releases file, without changes it is empty so the last delta is not sent again
+    if fetch_args.only_changes && upcoming_releases.is_empty() {
+        log::info!("No new or changed releases since last run");
+    }
+    releases::create_releases(
+        upcoming_releases,
+        &config.dest_release,
+        &config.release_file,
+        fetch_args.format,
+        fetch_args.locale,
+    )
+    .with_context(|| {
+        format!(
+            "Failed to create release file '{}', located in '{}'",
+            &config.release_file, &config.dest_release
+        )
+    })?;
+
+    // remember the releases of this run for the next run
+    if let (Some(state_file), Some(next_state)) = (&fetch_args.state_file, next_state) {
+        state::save_state(state_file, &next_state)
+            .with_context(|| format!("Failed to save state file '{}'", state_file))?;
+    }
+    if let (Some(history_file), Some(next_history)) = (&fetch_args.history_file, next_history) {
+        reissues::save_history(history_file, &next_history)
+            .with_context(|| format!("Failed to save history file '{}'", history_file))?;
+    }
+    if let (Some(details_cache), Some(next_cache)) = (&fetch_args.details_cache, next_details_cache)
+    {
+        details::save_cache(details_cache, &next_cache)
+            .with_context(|| format!("Failed to save details cache '{}'", details_cache))?;
+    }
+
+    Ok(())
+}
+
+/// Add, remove or list the authors of the authors file.
+///
+/// # Arguments
+///
+/// config - The configuration of this run
+/// command - The command to manage the authors file
+///
+/// # Return
+///
+/// Ok() - Successfully managed the authors file
+/// Err(err) - Some error occured
+fn manage_authors(config: &config::Config, command: &cli::AuthorsCommand) -> Result<()> {
+    log::trace!("main::manage_authors()");
+
+    let authors_file = &config.authors_file;
+
+    match command {
+        cli::AuthorsCommand::Add { author } => authors::add_author(authors_file, author)
+            .with_context(|| format!("Failed to add '{}' to '{}'", author, authors_file)),
+        cli::AuthorsCommand::Remove { author } => authors::remove_author(authors_file, author)
+            .with_context(|| format!("Failed to remove '{}' from '{}'", author, authors_file)),
+        cli::AuthorsCommand::List => {
+            let authors = authors::read_authors(authors_file)
+                .with_context(|| format!("Failed to extract authors from '{}'", authors_file))?;
+
+            for author in &authors {
+                println!("{}", authors::author_line(author));
+            }
+
+            Ok(())
+        }
+    }
+}
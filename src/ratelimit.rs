@@ -0,0 +1,94 @@
+//! This module limits the number of requests sent to a webpage, so the webpages are not flooded
+//! with requests.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio::time::{Duration, Instant};
+
+/// Custom data type to collect the state of the token bucket of a single host.
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token-bucket rate limiter with one bucket per host. Every request takes one token. The tokens
+/// are refilled with the configured rate up to the configured burst.
+pub struct RateLimiter {
+    requests_per_second: f64,
+    burst: f64,
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+}
+
+impl RateLimiter {
+    /// Create a rate limiter.
+    ///
+    /// # Arguments
+    ///
+    /// requests_per_second - Maximum number of requests per second sent to the same host. A rate
+    /// of zero or less, or one which is no number, disables the rate limiter
+    /// burst - Maximum number of requests sent at once to the same host (at least 1)
+    ///
+    /// # Return
+    ///
+    /// The rate limiter
+    pub fn create(requests_per_second: f64, burst: u32) -> Self {
+        RateLimiter {
+            requests_per_second,
+            burst: f64::from(burst.max(1)),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Wait until a request to the host of the given URL is allowed.
+    ///
+    /// # Arguments
+    ///
+    /// url - The URL the request is sent to
+    pub async fn acquire(&self, url: &str) {
+        log::trace!("ratelimit::acquire()");
+
+        // a rate of zero or less (or no number at all) disables the rate limiter
+        if self.requests_per_second.is_nan() || self.requests_per_second <= 0.0 {
+            return;
+        }
+
+        let host = host_of(url);
+
+        loop {
+            let wait = {
+                let mut buckets = self.buckets.lock().unwrap();
+                let now = Instant::now();
+                let bucket = buckets.entry(host.clone()).or_insert(TokenBucket {
+                    tokens: self.burst,
+                    last_refill: now,
+                });
+
+                // refill the tokens for the time passed since the last request
+                let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+                bucket.tokens =
+                    (bucket.tokens + elapsed * self.requests_per_second).min(self.burst);
+                bucket.last_refill = now;
+
+                if bucket.tokens >= 1.0 {
+                    bucket.tokens -= 1.0;
+                    return;
+                }
+
+                // a tiny rate results in a wait too long to be represented
+                Duration::try_from_secs_f64((1.0 - bucket.tokens) / self.requests_per_second)
+                    .unwrap_or(Duration::MAX)
+            };
+
+            log::trace!("Waiting {:?} before sending request to '{}'", wait, &host);
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// Get the host of an URL. If the URL cannot be parsed, the whole URL is used as host.
+fn host_of(url: &str) -> String {
+    match reqwest::Url::parse(url) {
+        Ok(parsed) => parsed.host_str().unwrap_or(url).to_string(),
+        Err(_) => url.to_string(),
+    }
+}
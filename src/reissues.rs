@@ -0,0 +1,302 @@
+//! This module detects reissues, box sets and special editions of already published titles.
+
+use crate::customtypes::{KnownTitle, Locale, UpcomingRelease};
+use crate::format;
+use crate::releases;
+use anyhow::{Context, Result};
+
+/// Words marking a reissue, a box set or a special edition. They are compared case-insensitively
+/// with the words of a result, a marker of several words like "Box-Set" matches "Box Set" as well.
+const MARKERS: [&str; 11] = [
+    "Neuausgabe",
+    "Neuauflage",
+    "Sonderausgabe",
+    "Jubiläumsausgabe",
+    "Geschenkausgabe",
+    "Sammelband",
+    "Sammelausgabe",
+    "Schuber",
+    "Box-Set",
+    "Boxset",
+    "Sammlerausgabe",
+];
+
+/// Phrases introducing the year a title was published first. "Erstausgabe" and "Originalausgabe"
+/// are left out, as they usually date the original of a translation instead of the German edition.
+const FIRST_PUBLICATION: [&str; 2] = ["Erstveröffentlichung", "erstmals erschienen"];
+
+/// Beginning of the reason of a reissue published first in an earlier year, e.g. "first published
+/// 1996". The reasons are stored in English and translated when rendered.
+const FIRST_PUBLISHED: &str = "first published ";
+
+/// Beginning of the reason of a reissue whose title is already published according to the
+/// history, e.g. "published 1996-03-01".
+const PUBLISHED: &str = "published ";
+
+/// Detect a reissue from the text of a search result. A search result is a reissue if it contains
+/// a marker like "Neuausgabe" or "Sonderausgabe", or if it was published first before the year of
+/// the release.
+///
+/// # Arguments
+///
+/// text - The text of the search result
+/// release_year - The year of the release
+///
+/// # Return
+///
+/// Some(String) - The reason the release is considered a reissue, e.g. "Sonderausgabe"
+/// None - No sign of a reissue is found
+pub fn detect_reissue(text: &str, release_year: i32) -> Option<String> {
+    log::trace!("reissues::detect_reissue()");
+
+    let words = lowercase_words(text);
+
+    if let Some(marker) = MARKERS.iter().find(|marker| {
+        let marker_words = lowercase_words(marker);
+        words
+            .windows(marker_words.len())
+            .any(|window| window == marker_words.as_slice())
+    }) {
+        return Some(marker.to_string());
+    }
+
+    match first_publication_year(text) {
+        Some(year) if year < release_year => Some(format!("{}{}", FIRST_PUBLISHED, year)),
+        _ => None,
+    }
+}
+
+/// Describe the reason a release is considered a reissue in the language of a locale. A marker
+/// like "Sonderausgabe" is taken from the search result and kept as it is.
+/// Example: "published 1996-03-01" is described as "erschienen am 1. März 1996" (de), "published
+/// March 1, 1996" (en) or "paru le 1er mars 1996" (fr)
+///
+/// # Arguments
+///
+/// reason - The reason as stored in the release
+/// locale - The language of the description
+///
+/// # Return
+///
+/// The description of the reason
+pub fn describe_reason(reason: &str, locale: Locale) -> String {
+    log::trace!("reissues::describe_reason()");
+
+    if let Some(year) = reason.strip_prefix(FIRST_PUBLISHED) {
+        return match locale {
+            Locale::De => format!("erstmals erschienen {}", year),
+            Locale::En => format!("first published {}", year),
+            Locale::Fr => format!("première parution {}", year),
+        };
+    }
+
+    match reason
+        .strip_prefix(PUBLISHED)
+        .and_then(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+    {
+        Some(date) => {
+            let date =
+                releases::format_date(&date.and_time(chrono::NaiveTime::MIN).and_utc(), locale);
+            match locale {
+                Locale::De => format!("erschienen am {}", date),
+                Locale::En => format!("published {}", date),
+                Locale::Fr => format!("paru le {}", date),
+            }
+        }
+        None => reason.to_string(),
+    }
+}
+
+/// Split a text into its lowercase words.
+fn lowercase_words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+/// Get the year a title was published first, e.g. from "Erstveröffentlichung: 1996". A year
+/// referring to the original, e.g. "Original-Erstveröffentlichung 2023" or "im Original erstmals
+/// erschienen 2023", is ignored, as it dates the original of a translation.
+fn first_publication_year(text: &str) -> Option<i32> {
+    let lowercase_text = text.to_lowercase();
+
+    FIRST_PUBLICATION.iter().find_map(|phrase| {
+        let phrase = phrase.to_lowercase();
+
+        lowercase_text
+            .match_indices(&phrase)
+            .filter(|(index, _)| {
+                let preceding = lowercase_text[..*index]
+                    .char_indices()
+                    .rev()
+                    .nth(11)
+                    .map_or(0, |(start, _)| start);
+                !lowercase_text[preceding..*index].contains("original")
+            })
+            .find_map(|(index, _)| {
+                // the year has to follow closely, e.g. "Erstveröffentlichung: 1996" or
+                // "erstmals erschienen im Jahr 1996"
+                let following: String = lowercase_text[index + phrase.len()..]
+                    .chars()
+                    .take(20)
+                    .collect();
+                following
+                    .split(|c: char| !c.is_ascii_digit())
+                    .find(|number| number.len() == 4)?
+                    .parse()
+                    .ok()
+            })
+    })
+}
+
+/// Mark the releases whose title is already known to be published as reissues. A title is known to
+/// be published if the history lists it for the same author with a release date before today and
+/// before the date of the release. A release found again with the same ISBN or product page is no
+/// reissue, its release was postponed. Releases already marked keep their reason.
+///
+/// # Arguments
+///
+/// releases - The upcoming releases
+/// history - The titles found in previous runs
+/// today - The current date
+pub fn mark_known_titles(
+    releases: &mut [UpcomingRelease],
+    history: &[KnownTitle],
+    today: chrono::NaiveDate,
+) {
+    log::trace!("reissues::mark_known_titles()");
+
+    for release in releases
+        .iter_mut()
+        .filter(|release| release.reissue.is_empty())
+    {
+        let title = format::normalize_title(&release.title);
+
+        let published = history.iter().find(|known| {
+            known.author == release.author
+                && format::normalize_title(&known.title) == title
+                && known.date.date_naive() < today
+                && known.date < release.date
+                && !is_same_product(known, release)
+        });
+
+        if let Some(known) = published {
+            log::debug!(
+                "'{}' of '{}' is already published on {}",
+                &release.title,
+                &release.author,
+                known.date.format("%Y-%m-%d")
+            );
+            release.reissue = format!("{}{}", PUBLISHED, known.date.format("%Y-%m-%d"));
+        }
+    }
+}
+
+/// Add the releases of this run to the titles found in previous runs. Every title is listed once
+/// per author with its earliest release date. A release found again with the same ISBN or product
+/// page takes its new date, as the earlier date was postponed.
+///
+/// # Arguments
+///
+/// history - The titles found in previous runs
+/// releases - The releases of this run
+///
+/// # Return
+///
+/// The titles found in this and all previous runs
+pub fn update_history(history: &[KnownTitle], releases: &[UpcomingRelease]) -> Vec<KnownTitle> {
+    log::trace!("reissues::update_history()");
+
+    let mut updated = history.to_vec();
+
+    for release in releases {
+        let title = format::normalize_title(&release.title);
+
+        match updated.iter_mut().find(|known| {
+            known.author == release.author && format::normalize_title(&known.title) == title
+        }) {
+            Some(known) if is_same_product(known, release) => known.date = release.date,
+            Some(known) if release.date < known.date => {
+                known.date = release.date;
+                known.isbn = release.isbn.clone();
+                known.url = release.url.clone();
+            }
+            Some(_) => {}
+            None => updated.push(KnownTitle {
+                author: release.author.clone(),
+                title: release.title.clone(),
+                date: release.date,
+                isbn: release.isbn.clone(),
+                url: release.url.clone(),
+            }),
+        }
+    }
+
+    updated
+}
+
+/// Check whether a release is the same product as a known title, i.e. they share the ISBN or the
+/// product page.
+fn is_same_product(known: &KnownTitle, release: &UpcomingRelease) -> bool {
+    (!release.isbn.is_empty() && known.isbn == release.isbn)
+        || (!release.url.is_empty() && known.url == release.url)
+}
+
+/// Load the titles found in previous runs from the history file.
+///
+/// # Arguments
+///
+/// path - Path to the history file
+///
+/// # Return
+///
+/// Ok(Vec<KnownTitle>) - The titles found in previous runs. Empty if there is no history file yet
+/// Err(err) - Some error occured
+pub fn load_history(path: &str) -> Result<Vec<KnownTitle>> {
+    log::trace!("reissues::load_history()");
+
+    // no history file means there was no previous run
+    if !std::path::Path::new(path).exists() {
+        log::info!("History file '{}' not found, no title is known yet", path);
+        return Ok(Vec::new());
+    }
+
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read history file '{}'", path))?;
+    let history: Vec<KnownTitle> = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse history file '{}'", path))?;
+
+    log::debug!("Loaded {} known titles from '{}'", history.len(), path);
+
+    Ok(history)
+}
+
+/// Save the titles found in this and all previous runs to the history file.
+///
+/// # Arguments
+///
+/// path - Path to the history file
+/// history - The titles to save
+///
+/// # Return
+///
+/// Ok() - Successfully saved the titles
+/// Err(err) - Some error occured
+pub fn save_history(path: &str, history: &[KnownTitle]) -> Result<()> {
+    log::trace!("reissues::save_history()");
+
+    if let Some(parent) = std::path::Path::new(path).parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory of history file '{}'", path))?;
+    }
+
+    let json = serde_json::to_string_pretty(history)
+        .with_context(|| "Failed to serialize known titles to JSON")?;
+    std::fs::write(path, json + "\n")
+        .with_context(|| format!("Failed to write history file '{}'", path))?;
+
+    log::debug!("Saved {} known titles to '{}'", history.len(), path);
+
+    Ok(())
+}
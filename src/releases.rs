@@ -1,17 +1,26 @@
 //! This module processes the upcoming releases for given authors and write them to a file.
 
-use crate::customtypes::UpcomingRelease;
+use crate::customtypes::{Locale, OutputFormat, UpcomingRelease};
+use crate::export;
+use crate::reissues;
+use crate::series;
 use anyhow::{Context, Result};
 use chrono::Datelike;
-use std::io::Write;
+use std::fmt::Write;
 
-/// Write the releases to a destination file. All releases are sorted by date.
+/// Destination which streams the releases to stdout instead of writing a release file.
+pub const STDOUT: &str = "-";
+
+/// Write the releases to a destination file. All releases are sorted by date. Without releases, the
+/// release file is written without entries, e.g. only the heading of the text format.
 ///
 /// # Arguments
 ///
-/// releases – The upcoming releases.
-/// destination – The destination path for the releases file
+/// releases – The upcoming releases. Could be empty for no upcoming release
+/// destination – The destination path for the releases file. "-" streams the releases to stdout
 /// file_name – The file name of the release list to be created
+/// output_format - The format of the release file
+/// locale - The language of the human-readable release file
 ///
 /// # Return
 ///
@@ -21,21 +30,55 @@ pub fn create_releases(
     mut releases: Vec<UpcomingRelease>,
     destination: &str,
     file_name: &str,
+    output_format: OutputFormat,
+    locale: Locale,
 ) -> Result<()> {
     log::trace!("releases::create_releases()");
 
-    // first of all, given Vector should not be empty
+    // no upcoming release is a valid result, the previous release file is outdated anyway
     if releases.is_empty() {
-        anyhow::bail!("No releases existing!");
+        log::info!("No upcoming releases found");
     }
-    // also the destination path should exist. If no, create it
-    std::fs::create_dir_all(destination)
-        .with_context(|| format!("Failed to create destination dir '{}'", destination))?;
-
     log::debug!("Got {} releases to process", releases.len());
 
+    for release in &releases {
+        if release.author.is_empty() {
+            anyhow::bail!("No author given: {:?}", release);
+        }
+        if release.title.is_empty() {
+            anyhow::bail!("No book title given: {:?}", release);
+        }
+
+        log::debug!("Release to process: {:#?}", release);
+    }
+
     // sort releases by date
-    releases.sort_by(|a, b| a.date.cmp(&b.date));
+    releases.sort_by_key(|release| release.date);
+
+    // render the sorted data in the requested format
+    let contents = match output_format {
+        OutputFormat::Text => render_text(&releases, locale)?,
+        OutputFormat::Csv => export::to_csv(&releases),
+        OutputFormat::Json => export::to_json(&releases)?,
+        OutputFormat::Ndjson => export::to_ndjson(&releases)?,
+        OutputFormat::Ics => export::to_ics(&releases, locale),
+    };
+
+    // stream the sorted data to stdout, so it can be piped to other tools
+    if destination == STDOUT {
+        let mut stdout = std::io::stdout().lock();
+        std::io::Write::write_all(&mut stdout, contents.as_bytes())
+            .and_then(|_| std::io::Write::flush(&mut stdout))
+            .with_context(|| "Failed to write releases to stdout")?;
+
+        log::info!("Successfully wrote releases to stdout");
+
+        return Ok(());
+    }
+
+    // also the destination path should exist. If no, create it
+    std::fs::create_dir_all(destination)
+        .with_context(|| format!("Failed to create destination dir '{}'", destination))?;
 
     // now write the sorted data into a file
     let releases_path = destination.to_owned() + "/" + file_name;
@@ -50,95 +93,334 @@ pub fn create_releases(
         })?;
     }
 
-    let mut releases_file = std::fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(releases_path.clone())
-        .with_context(|| {
-            format!(
-                "Failed to create/append/open release file '{}'",
-                &releases_path
-            )
-        })?;
+    std::fs::write(&releases_path, contents)
+        .with_context(|| format!("Failed to write release file '{}'", &releases_path))?;
 
-    // set title of releases file
-    writeln!(releases_file, "Upcoming Book Releases")?;
+    log::info!("Successfully created releases file '{}'", &releases_path);
 
-    // Collect upcoming releases under same date if happening
-    let mut formatted_time_global = "".to_string();
+    Ok(())
+}
 
-    for release in &releases {
-        if release.author.is_empty() {
-            anyhow::bail!("No author given: {:?}", release);
-        }
-        if release.title.is_empty() {
-            anyhow::bail!("No book title given: {:?}", release);
-        }
+/// Read the release file of the last run.
+///
+/// # Arguments
+///
+/// destination – The destination path of the releases file. "-" (stdout) is an error
+/// file_name – The file name of the release list
+///
+/// # Return
+///
+/// Ok(String) - The contents of the release file
+/// Err(err) - Some error occured
+pub fn read_releases(destination: &str, file_name: &str) -> Result<String> {
+    log::trace!("releases::read_releases()");
 
-        log::debug!("Release to process: {:#?}", release);
+    // a release file printed to stdout was never stored
+    if destination == STDOUT {
+        anyhow::bail!(
+            "Release file is printed to stdout (no destination path or \"{}\"), there is no file to show",
+            STDOUT
+        );
+    }
 
-        // make date more human-readable (and print it in german date format)
-        let formatted_time_local = format!(
-            "{}. {} {}",
-            release.date.day(),
-            month_name_german(release.date.month()),
-            release.date.year()
+    let releases_path = destination.to_owned() + "/" + file_name;
+
+    if !std::path::Path::new(&releases_path).exists() {
+        anyhow::bail!(
+            "Release file '{}' not found, search for upcoming releases first",
+            &releases_path
         );
+    }
+
+    std::fs::read_to_string(&releases_path)
+        .with_context(|| format!("Failed to read release file '{}'", &releases_path))
+}
+
+/// Render the releases as human-readable list. Releases on the same date are collected under the
+/// localized date. The link to the product page and the further editions are listed below their
+/// release, the progress of every series at the end.
+///
+/// # Arguments
+///
+/// releases – The upcoming releases, sorted by date
+/// locale - The language of the heading and the dates
+///
+/// # Return
+///
+/// Ok(String) - The rendered list
+/// Err(err) - Some error occured
+pub fn render_text(releases: &[UpcomingRelease], locale: Locale) -> Result<String> {
+    log::trace!("releases::render_text()");
+
+    let mut contents = String::new();
+
+    // set title of releases file
+    writeln!(contents, "{}", heading(locale))?;
+
+    // Collect upcoming releases under same date if happening
+    let mut formatted_time_global = "".to_string();
+
+    for release in releases {
+        // make date more human-readable
+        let formatted_time_local = format_date(&release.date, locale);
 
         if formatted_time_global != formatted_time_local {
             formatted_time_global = formatted_time_local;
 
-            writeln!(releases_file)?;
-            writeln!(releases_file, "{}", &formatted_time_global).with_context(|| {
-                format!(
-                    "Failed to write date to release file '{:?}'",
-                    &releases_path
-                )
-            })?;
-            writeln!(releases_file, "-----------------------------------------------------------------------------------")?
+            writeln!(contents)?;
+            writeln!(contents, "{}", &formatted_time_global)?;
+            writeln!(
+                contents,
+                "-----------------------------------------------------------------------------------"
+            )?
         }
 
-        writeln!(
-            releases_file,
-            "{} - \"{}\"",
-            &release.author, &release.title
-        )
-        .with_context(|| {
+        // record the series, format, publisher, price, the release source the release was found
+        // on, the ISBN and why it is a reissue
+        let series = match release.volume {
+            Some(volume) if !release.series.is_empty() => {
+                format!("{} {}", &release.series, series::volume_label(volume))
+            }
+            _ => release.series.clone(),
+        };
+        // "ISBN" is the same in every locale
+        let isbn = if release.isbn.is_empty() {
+            String::new()
+        } else {
+            format!("ISBN {}", &release.isbn)
+        };
+        let reissue = if release.reissue.is_empty() {
+            String::new()
+        } else {
             format!(
-                "Failed to write author '{}' and title '{}' into release file '{}'",
-                &release.author, &release.title, &releases_path
+                "{}: {}",
+                reissue_label(locale),
+                reissues::describe_reason(&release.reissue, locale)
             )
-        })?;
+        };
+        let source = details(&[
+            &series,
+            &release.format,
+            &release.publisher,
+            &release.price,
+            &release.source,
+            &isbn,
+            &reissue,
+        ]);
+
+        // the language is only of interest if the work is released in several languages
+        let multilingual = release
+            .editions
+            .iter()
+            .any(|edition| edition.language != release.language);
+        let language = if multilingual {
+            format!(" [{}]", &release.language)
+        } else {
+            String::new()
+        };
+
+        writeln!(
+            contents,
+            "{} - {}{}{}",
+            &release.author,
+            quote(&release.title, locale),
+            &source,
+            &language
+        )?;
+
+        // the product page is given in angle brackets to set it apart from the editions, the cover
+        // image is labeled below it
+        if !release.url.is_empty() {
+            writeln!(contents, "    <{}>", &release.url)?;
+        }
+        if !release.cover_url.is_empty() {
+            writeln!(
+                contents,
+                "    {}: <{}>",
+                cover_label(locale),
+                &release.cover_url
+            )?;
+        }
+
+        // list the editions in other formats, languages or from other sources below the release
+        for edition in &release.editions {
+            let language = if multilingual {
+                format!("{}: ", &edition.language)
+            } else {
+                String::new()
+            };
+
+            writeln!(
+                contents,
+                "    {}{} {}{}",
+                &language,
+                quote(&edition.title, locale),
+                format_date(&edition.date, locale),
+                details(&[&edition.format, &edition.source])
+            )?;
+        }
     }
 
-    log::info!("Successfully created releases file '{}'", &releases_path);
+    // show the progress of every series with upcoming volumes
+    let progress = series::series_progress(releases, locale);
+    if !progress.is_empty() {
+        writeln!(contents)?;
+        writeln!(contents, "{}", series_heading(locale))?;
+        writeln!(
+            contents,
+            "-----------------------------------------------------------------------------------"
+        )?;
+        for line in progress {
+            writeln!(contents, "{}", line)?;
+        }
+    }
 
-    Ok(())
+    Ok(contents)
 }
 
-/// Map month number to german month name.
+/// Join the details of a release, e.g. format and release source, to " (format, source)". Unknown
+/// details are left out.
+fn details(details: &[&str]) -> String {
+    let details: Vec<&str> = details
+        .iter()
+        .copied()
+        .filter(|detail| !detail.is_empty())
+        .collect();
+
+    if details.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", details.join(", "))
+    }
+}
+
+/// Get the heading of the human-readable list.
+fn heading(locale: Locale) -> &'static str {
+    match locale {
+        Locale::De | Locale::En => "Upcoming Book Releases",
+        Locale::Fr => "Prochaines parutions",
+    }
+}
+
+/// Get the heading of the series progress.
+fn series_heading(locale: Locale) -> &'static str {
+    match locale {
+        Locale::De => "Reihen",
+        Locale::En => "Series",
+        Locale::Fr => "Séries",
+    }
+}
+
+/// Get the label of the reason a release is considered a reissue.
+fn reissue_label(locale: Locale) -> &'static str {
+    match locale {
+        Locale::De => "Neuauflage",
+        Locale::En => "reissue",
+        Locale::Fr => "réédition",
+    }
+}
+
+/// Get the label of the cover image.
+fn cover_label(locale: Locale) -> &'static str {
+    match locale {
+        Locale::De | Locale::En => "Cover",
+        Locale::Fr => "Couverture",
+    }
+}
+
+/// Format a release date in the order and with the month names of a locale.
+/// Example: 1 September 2024 is formatted as "1. September 2024" (de), "September 1, 2024" (en) or
+/// "1er septembre 2024" (fr)
+///
+/// # Arguments
+///
+/// date - The release date
+/// locale - The language of the date
+///
+/// # Return
+///
+/// The formatted date
+pub fn format_date(date: &chrono::DateTime<chrono::Utc>, locale: Locale) -> String {
+    let (day, month, year) = (date.day(), month_name(date.month(), locale), date.year());
+
+    match locale {
+        Locale::De => format!("{}. {} {}", day, month, year),
+        Locale::En => format!("{} {}, {}", month, day, year),
+        Locale::Fr if day == 1 => format!("1er {} {}", month, year),
+        Locale::Fr => format!("{} {} {}", day, month, year),
+    }
+}
+
+/// Put a title in the quotation marks of a locale.
+pub fn quote(title: &str, locale: Locale) -> String {
+    match locale {
+        Locale::De | Locale::En => format!("\"{}\"", title),
+        Locale::Fr => format!("« {} »", title),
+    }
+}
+
+/// Map month number to the month name of a locale.
 ///
 /// # Arguments
 ///
 /// month - The month number
+/// locale - The language of the month name
 ///
 /// # Return
 ///
-/// The matching german month name
-fn month_name_german(month: u32) -> &'static str {
+/// The matching month name
+fn month_name(month: u32, locale: Locale) -> &'static str {
+    const GERMAN: [&str; 12] = [
+        "Januar",
+        "Februar",
+        "März",
+        "April",
+        "Mai",
+        "Juni",
+        "Juli",
+        "August",
+        "September",
+        "Oktober",
+        "November",
+        "Dezember",
+    ];
+    const ENGLISH: [&str; 12] = [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ];
+    const FRENCH: [&str; 12] = [
+        "janvier",
+        "février",
+        "mars",
+        "avril",
+        "mai",
+        "juin",
+        "juillet",
+        "août",
+        "septembre",
+        "octobre",
+        "novembre",
+        "décembre",
+    ];
+
+    let names = match locale {
+        Locale::De => &GERMAN,
+        Locale::En => &ENGLISH,
+        Locale::Fr => &FRENCH,
+    };
+
     match month {
-        1 => "Januar",
-        2 => "Februar",
-        3 => "März",
-        4 => "April",
-        5 => "Mai",
-        6 => "Juni",
-        7 => "Juli",
-        8 => "August",
-        9 => "September",
-        10 => "Oktober",
-        11 => "November",
-        12 => "Dezember",
+        1..=12 => names[month as usize - 1],
         _ => "Unknown",
     }
 }
@@ -1,172 +1,79 @@
 //! This module scrapes HTML files from a webpage to get information about upcoming releases.
 
-use crate::customtypes::UpcomingRelease;
+use crate::customtypes::{Author, ScrapeResult, UpcomingRelease};
+use crate::fetcher::Fetcher;
 use crate::format;
-use anyhow::{Context, Result};
+use crate::sources::ReleaseSource;
+use anyhow::Result;
+use futures::StreamExt;
 
-const WELTBILD_URL: &str = "https://www.weltbild.de";
-const SEARCH: &str = "/suche/";
-const RELEASE_YEAR: &str = "?jahr=0";
-const TYPE: &str = "&node=%2Fbuecher";
-const LANGUAGE: &str = "&sprache=%2Flanguage%2Fger";
-static ONLY_BOOKS: [&str; 2] = ["Taschenbuch", "Buch"];
-
-/// Navigate to respective Weltbild URL and parse html contents to get potential upcoming release
-/// per author.
+/// Search the chain of release sources for the potential upcoming releases of every author. The
+/// authors are looked up concurrently, but the releases keep the order of the authors. If an author
+/// could not be checked, the other authors are still processed.
 ///
 /// # Arguments
 ///
 /// authors - A list of authors to get the potential upcoming releases
+/// sources - The ordered chain of release sources
+/// fetcher - The fetcher used to get the HTML contents
+/// concurrency - Maximum number of authors looked up at the same time
 ///
 /// # Return
 ///
-/// Ok(Vec<UpcomingRelease>) - A list of upcoming releases. Could be empty for no upcoming release
+/// Ok(ScrapeResult) - A list of upcoming releases and the authors which could not be checked
 /// Err(err) - Some error occured
-pub async fn parse_contents(authors: Vec<String>) -> Result<Vec<UpcomingRelease>> {
+pub async fn parse_contents(
+    authors: Vec<Author>,
+    sources: &[Box<dyn ReleaseSource>],
+    fetcher: &Fetcher,
+    concurrency: usize,
+) -> Result<ScrapeResult> {
     log::trace!("scraper::parse_contents()");
 
-    // first of all, check whether list of authors is empty or not
+    // first of all, check whether list of authors or sources is empty or not
     if authors.is_empty() {
         anyhow::bail!("List contains no authors!");
     }
+    if sources.is_empty() {
+        anyhow::bail!("List contains no release sources!");
+    }
     log::info!("Number of authors to be processed: {}", &authors.len());
 
-    // Create vector of upcoming releases
-    let mut upcoming_releases: Vec<UpcomingRelease> = Vec::new();
-
-    // Create reqwest client
-    let client = reqwest::Client::new();
-    let mut releasing_authors = std::collections::HashSet::new();
-
-    // now get the data from Weltbild.de
-    // WELTBILD_URL: The URL to Weltbild.de
-    // SEARCH: Initiate a search
-    // RELEASE_YEAR: We want the current year
-    // TYPE: We want book, no audibles or something similar
-    // LANGUAGE: We only want books in german language
-    for (index, author) in authors.iter().enumerate() {
-        log::info!(
-            "Processing author '{}' ({}/{})",
-            &author,
-            &index + 1,
-            &authors.len()
-        );
-
-        let updated_author = author.replace(", ", "+");
-        let url =
-            WELTBILD_URL.to_owned() + SEARCH + &updated_author + RELEASE_YEAR + TYPE + LANGUAGE;
-
-        log::debug!("URL to check: '{}'", &url);
-
-        // Send a GET request to the URL and retrieve the response
-        let response = client
-            .get(url.clone())
-            .send()
-            .await
-            .with_context(|| format!("Failed to send HTTP GET request to '{}'", &url))?;
-
-        // Check if the request was successful
-        if response.status().is_success() {
+    // look up the authors concurrently. "buffered" keeps the results in the order of the authors
+    let results: Vec<Result<Vec<UpcomingRelease>>> = futures::stream::iter(authors.iter())
+        .enumerate()
+        .map(|(index, author)| {
             log::info!(
-                "Request was successful! Parsing HTML contents for: '{}'",
-                &author
+                "Processing author '{}' ({}/{})",
+                &author.name,
+                &index + 1,
+                &authors.len()
             );
+            search_author(author, sources, fetcher)
+        })
+        .buffered(concurrency.max(1))
+        .collect()
+        .await;
+
+    // Collect the upcoming releases and the authors which could not be checked
+    let mut scrape_result = ScrapeResult::default();
+    let mut releasing_authors = std::collections::HashSet::new();
 
-            // Read the response body as a string
-            let html_content = response
-                .text()
-                .await
-                .with_context(|| "Failed to get HTML content")?;
-
-            // Parse the HTML content
-            let document = scraper::Html::parse_document(&html_content);
-
-            // Define a selector to find all <div class="inner-flex-container"> tag
-            let div_selector = scraper::Selector::parse("div.inner-flex-container").unwrap();
-            let matching_divs = document.select(&div_selector);
-
-            // Iterate over three elements (there should be no more upcoming releases per author)
-            for div_elem in matching_divs.take(3) {
-                // remove trailing whitespaces and blank lines from string
-                let raw_content = div_elem.text().collect::<String>();
-                let formatted_content = raw_content
-                    .lines()
-                    .filter(|line| !line.trim().is_empty())
-                    .map(|line| line.trim())
-                    .collect::<Vec<_>>()
-                    .join("\n");
-
-                log::trace!(
-                    "Formatted HTML content for '{}':\n{:?}",
-                    &author,
-                    &formatted_content
-                );
-
-                // rearrange the author name to search in the formatted content
-                // if the author is not found, no upcoming release is available. Continue the for
-                // loop then
-                let formatted_author = match format::format_author_name(author) {
-                    Ok(rearranged) => rearranged,
-                    Err(err) => {
-                        log::trace!(
-                            "Failed to get formatted author name for '{}': {}",
-                            &author,
-                            err
-                        );
-                        continue;
-                    }
-                };
-
-                if formatted_content.contains(&formatted_author)
-                    && ONLY_BOOKS
-                        .iter()
-                        .any(|&sub| formatted_content.contains(sub))
-                {
-                    let formatted_title =
-                        match format::format_release_title(&formatted_content, &formatted_author) {
-                            Ok(title) => title,
-                            Err(err) => {
-                                log::trace!(
-                                    "Failed to get formatted release title for '{}': {}",
-                                    &author,
-                                    err
-                                );
-                                continue;
-                            }
-                        };
-
-                    let formatted_date = match format::format_release_date(&formatted_content) {
-                        Ok(date) => date,
-                        Err(err) => {
-                            log::trace!("Failed to get formatted date for '{}': {}", &author, err);
-                            continue;
-                        }
-                    };
-
-                    log::info!(
-                        "Upcoming release '{}' for '{}' available!",
-                        &formatted_title,
-                        &formatted_author
-                    );
-                    let upcoming_release: UpcomingRelease = UpcomingRelease::create(
-                        formatted_author.clone(),
-                        formatted_title,
-                        formatted_date,
-                    );
-                    upcoming_releases.push(upcoming_release);
-                    releasing_authors.insert(formatted_author);
+    for (author, result) in authors.iter().zip(results) {
+        match result {
+            Ok(upcoming_releases) => {
+                for upcoming_release in upcoming_releases {
+                    releasing_authors.insert(upcoming_release.author.clone());
+                    scrape_result.releases.push(upcoming_release);
                 }
             }
-        } else {
-            anyhow::bail!(
-                "Request for author '{}' failed with status code: {:?}",
-                &author,
-                response.status()
-            );
+            Err(err) => {
+                log::error!("Failed to check author '{}': {:#}", author.name, err);
+                scrape_result
+                    .failed_authors
+                    .push((author.clone(), format!("{:#}", err)));
+            }
         }
-
-        // wait one second before doing the next request
-        std::thread::sleep(std::time::Duration::from_secs(1));
     }
 
     log::info!(
@@ -174,5 +81,68 @@ pub async fn parse_contents(authors: Vec<String>) -> Result<Vec<UpcomingRelease>
         &releasing_authors.len(),
         &authors.len()
     );
-    Ok(upcoming_releases)
+    Ok(scrape_result)
+}
+
+/// Search the chain of release sources for the potential upcoming releases of an author. The
+/// sources are asked in the given order. If a source fails or finds nothing for the author, the
+/// next source is asked.
+///
+/// # Arguments
+///
+/// author - The author to get the potential upcoming releases. If it is a pseudonym, the releases
+/// are reported for the author behind it
+/// sources - The ordered chain of release sources
+/// fetcher - The fetcher used to get the HTML contents
+///
+/// # Return
+///
+/// Ok(Vec<UpcomingRelease>) - A list of upcoming releases. Could be empty for no upcoming release
+/// Err(err) - Every release source failed
+async fn search_author(
+    author: &Author,
+    sources: &[Box<dyn ReleaseSource>],
+    fetcher: &Fetcher,
+) -> Result<Vec<UpcomingRelease>> {
+    log::trace!("scraper::search_author()");
+
+    let release_author = format::format_author_name(author.release_author())
+        .unwrap_or(author.release_author().to_string());
+    let tag = author.tag.clone().unwrap_or_default();
+
+    let mut last_error = None;
+    let mut succeeded = false;
+
+    for source in sources {
+        match source.search(fetcher, author).await {
+            Ok(found) if found.is_empty() => {
+                log::info!(
+                    "{} found no upcoming release for '{}'",
+                    source.name(),
+                    author.name
+                );
+                succeeded = true;
+            }
+            Ok(mut found) => {
+                for upcoming_release in &mut found {
+                    upcoming_release.author = release_author.clone();
+                    upcoming_release.source = source.name().to_string();
+                    upcoming_release.tag = tag.clone();
+                }
+                return Ok(found);
+            }
+            Err(err) => {
+                log::warn!("{} failed for '{}': {:#}", source.name(), author.name, err);
+                last_error = Some(err);
+            }
+        }
+    }
+
+    // only give up if every source failed
+    match (succeeded, last_error) {
+        (false, Some(err)) => {
+            Err(err.context(format!("All release sources failed for '{}'", author.name)))
+        }
+        _ => Ok(Vec::new()),
+    }
 }
@@ -0,0 +1,159 @@
+//! This module detects the series and volume embedded in the title of a release, e.g.
+//! "Verraten / Carl Mørck. Sonderdezernat Q Bd.10".
+
+use crate::customtypes::{Locale, UpcomingRelease};
+use crate::releases;
+
+/// Custom data type of a title split into the title itself, its series and its volume.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeriesTitle {
+    pub title: String,
+    /// Name of the series (empty if none)
+    pub series: String,
+    /// Volume in the series (None if none)
+    pub volume: Option<u32>,
+}
+
+/// Split a title into the title itself, the series and the volume. The series follows the title
+/// after a slash and ends with the volume, e.g. "Knochenkälte / David Hunter Bd.7". A series
+/// name with a leading hero, e.g. "Carl Mørck. Sonderdezernat Q", is reduced to the last part.
+/// Without a slash, the series is not named explicitly, e.g. "Der Totenarzt Teil 2", so the title
+/// is kept as it is.
+///
+/// # Arguments
+///
+/// title - The title as found on the release source
+///
+/// # Return
+///
+/// The title split into title, series and volume. Titles without explicit series are kept as they
+/// are
+pub fn parse_series(title: &str) -> SeriesTitle {
+    log::trace!("series::parse_series()");
+
+    let unchanged = SeriesTitle {
+        title: title.to_string(),
+        series: String::new(),
+        volume: None,
+    };
+
+    let Some((clean_title, series)) = title.rsplit_once(" / ") else {
+        return unchanged;
+    };
+
+    // the volume is given as e.g. "Bd.10", "Bd. 10", "Band 10" or "Teil 10", maybe in brackets
+    let re =
+        regex::Regex::new(r"(?i)^(.*?)[\s,:(-]*\b(?:Bd\.?|Band|Teil|Vol\.?)\s*(\d+)\)?$").unwrap();
+    let Some(captures) = re.captures(series.trim()) else {
+        return unchanged;
+    };
+
+    // the hero of the series is often named first, e.g. "Carl Mørck. Sonderdezernat Q"
+    let series_name = without_hero(&captures[1]).trim().to_string();
+    if series_name.is_empty() {
+        return unchanged;
+    }
+
+    SeriesTitle {
+        title: clean_title.trim().to_string(),
+        series: series_name,
+        volume: captures[2].parse().ok(),
+    }
+}
+
+/// Drop the hero named in front of a series name, e.g. "Carl Mørck. Sonderdezernat Q" is reduced
+/// to "Sonderdezernat Q". Abbreviations like "Dr." are no end of a hero name.
+fn without_hero(series: &str) -> &str {
+    let hero_end = series.rmatch_indices(". ").find(|(index, _)| {
+        let last_word = series[..*index].rsplit(' ').next().unwrap_or_default();
+        last_word.chars().count() > 3
+    });
+
+    match hero_end {
+        Some((index, separator)) => &series[index + separator.len()..],
+        None => series,
+    }
+}
+
+/// Summarize the upcoming volumes of every series, e.g.
+/// "Jussi Adler-Olsen - Sonderdezernat Q: Bd.9 und Bd.10 angekündigt". The series are sorted by
+/// author and name, the volumes by number. Releases of a series without volume are listed by title.
+///
+/// # Arguments
+///
+/// releases - The upcoming releases
+/// locale - The language of the summary
+///
+/// # Return
+///
+/// One line per series. Empty if no release belongs to a series
+pub fn series_progress(releases: &[UpcomingRelease], locale: Locale) -> Vec<String> {
+    log::trace!("series::series_progress()");
+
+    let mut series: Vec<(&str, &str)> = releases
+        .iter()
+        .filter(|release| !release.series.is_empty())
+        .map(|release| (release.author.as_str(), release.series.as_str()))
+        .collect();
+    series.sort();
+    series.dedup();
+
+    series
+        .into_iter()
+        .map(|(author, name)| {
+            let mut volumes: Vec<&UpcomingRelease> = releases
+                .iter()
+                .filter(|release| release.author == author && release.series == name)
+                .collect();
+
+            // unknown volumes come last
+            volumes.sort_by_key(|release| (release.volume.is_none(), release.volume));
+
+            let mut volumes: Vec<String> = volumes
+                .iter()
+                .map(|release| match release.volume {
+                    Some(volume) => volume_label(volume),
+                    None => releases::quote(&release.title, locale),
+                })
+                .collect();
+            volumes.dedup();
+
+            format!(
+                "{} - {}: {} {}",
+                author,
+                name,
+                enumerate(&volumes, locale),
+                upcoming_label(locale)
+            )
+        })
+        .collect()
+}
+
+/// Get the label of a volume, e.g. "Bd.10".
+pub fn volume_label(volume: u32) -> String {
+    format!("Bd.{}", volume)
+}
+
+/// Join the items to "a, b and c" in the language of a locale.
+fn enumerate(items: &[String], locale: Locale) -> String {
+    let conjunction = match locale {
+        Locale::De => "und",
+        Locale::En => "and",
+        Locale::Fr => "et",
+    };
+
+    match items {
+        [] => String::new(),
+        [item] => item.clone(),
+        [init @ .., last] => format!("{} {} {}", init.join(", "), conjunction, last),
+    }
+}
+
+/// Get the label of the upcoming volumes of a series.
+fn upcoming_label(locale: Locale) -> &'static str {
+    match locale {
+        Locale::De => "angekündigt",
+        Locale::En => "upcoming",
+        Locale::Fr => "à paraître",
+    }
+}
@@ -0,0 +1,546 @@
+//! This module defines the interface a webpage has to provide to be used as source for upcoming
+//! releases.
+
+mod hugendubel;
+mod thalia;
+mod weltbild;
+
+pub use hugendubel::Hugendubel;
+pub use thalia::Thalia;
+pub use weltbild::Weltbild;
+
+use crate::config::SearchConfig;
+use crate::customtypes::{Author, MediaFormat, UpcomingRelease};
+use crate::fetcher::Fetcher;
+use crate::format;
+use crate::reissues;
+use crate::series;
+use anyhow::Result;
+use chrono::Datelike;
+
+/// Prefix of the text line naming the publisher of a search result.
+const PUBLISHER_PREFIX: &str = "Verlag:";
+
+/// Maximum number of words of a text line holding the book format, e.g. "Hörbuch-Download (MP3)".
+/// Longer lines are part of a blurb, which may mention other formats.
+const FORMAT_LINE_WORDS: usize = 4;
+
+/// A webpage which can be searched for the upcoming releases of an author.
+#[async_trait::async_trait]
+pub trait ReleaseSource: Send + Sync {
+    /// Human-readable name of the source, e.g. "Weltbild".
+    fn name(&self) -> &'static str;
+
+    /// The search parameters of the source, e.g. the maximum number of results.
+    fn search_config(&self) -> &SearchConfig;
+
+    /// Get the languages to search separately for an author. An empty language means the source
+    /// is searched without language filter, which is the default.
+    ///
+    /// # Arguments
+    ///
+    /// author - The author to search for
+    ///
+    /// # Return
+    ///
+    /// The languages to search, at least one
+    fn languages(&self, _author: &Author) -> Vec<String> {
+        vec![String::new()]
+    }
+
+    /// Build the URL of the search result page for an author.
+    ///
+    /// # Arguments
+    ///
+    /// author - The author to search for
+    /// language - The language to search, empty for no language filter
+    ///
+    /// # Return
+    ///
+    /// Ok(String) - The URL of the search result page
+    /// Err(err) - Some error occured
+    fn search_url(&self, author: &Author, language: &str) -> Result<String>;
+
+    /// Parse the upcoming releases of an author from a search result page.
+    ///
+    /// # Arguments
+    ///
+    /// html_content - The HTML content of the search result page
+    /// author - The author to search for
+    ///
+    /// # Return
+    ///
+    /// A list of upcoming releases. Could be empty for no upcoming release
+    fn parse(&self, html_content: &str, author: &Author) -> Vec<UpcomingRelease>;
+
+    /// Get the URL of the next search result page. By default, only the first page is searched.
+    ///
+    /// # Arguments
+    ///
+    /// html_content - The HTML content of the current search result page
+    ///
+    /// # Return
+    ///
+    /// Some(String) - The URL of the next search result page
+    /// None - There is no next page
+    fn next_page(&self, _html_content: &str) -> Option<String> {
+        None
+    }
+
+    /// Search the source for upcoming releases of an author. Every language is searched
+    /// separately and recorded on the releases found. The search result pages are followed until
+    /// a page contains no upcoming release of the author, the maximum number of pages is reached
+    /// or more than the maximum number of results are found. In the latter case, the surplus
+    /// results are dropped with a warning.
+    ///
+    /// # Arguments
+    ///
+    /// fetcher - The fetcher used to get the search result page
+    /// author - The author to search for
+    ///
+    /// # Return
+    ///
+    /// Ok(Vec<UpcomingRelease>) - A list of upcoming releases. Could be empty for no upcoming release
+    /// Err(err) - Some error occured
+    async fn search(&self, fetcher: &Fetcher, author: &Author) -> Result<Vec<UpcomingRelease>> {
+        let search = self.search_config().for_author(author);
+        let limit_reached = |found: usize| search.max_results > 0 && found > search.max_results;
+
+        let mut upcoming_releases = Vec::new();
+
+        for language in self.languages(author) {
+            if limit_reached(upcoming_releases.len()) {
+                break;
+            }
+
+            let language_code = format::language_code(&language);
+            let mut next_url = Some(self.search_url(author, &language)?);
+            let mut pages = 0;
+
+            while let Some(url) = next_url.take() {
+                let html_content = fetcher.fetch(&url).await?;
+                pages += 1;
+
+                let found = self.parse(&html_content, author);
+                if found.is_empty() {
+                    break;
+                }
+
+                for mut upcoming_release in found {
+                    upcoming_release.language = language_code.clone();
+                    upcoming_releases.push(upcoming_release);
+                }
+
+                if limit_reached(upcoming_releases.len()) || pages >= search.max_pages {
+                    break;
+                }
+                next_url = self.next_page(&html_content);
+            }
+        }
+
+        if limit_reached(upcoming_releases.len()) {
+            log::warn!(
+                "{} found more than {} upcoming releases for '{}', the others are dropped. \
+                 Increase the maximum number of results to get all of them",
+                self.name(),
+                search.max_results,
+                author.name
+            );
+            upcoming_releases.truncate(search.max_results);
+        }
+
+        Ok(upcoming_releases)
+    }
+}
+
+/// Create the chain of release sources in the given order.
+///
+/// # Arguments
+///
+/// names - The names of the sources (weltbild, thalia, hugendubel), case-insensitive
+/// search - The search parameters, e.g. the accepted book formats
+/// today - The current date. Releases before it are no upcoming releases
+///
+/// # Return
+///
+/// Ok(Vec<Box<dyn ReleaseSource>>) - The release sources in the given order
+/// Err(err) - Some error occured
+pub fn create_sources(
+    names: &[String],
+    search: &SearchConfig,
+    today: chrono::NaiveDate,
+) -> Result<Vec<Box<dyn ReleaseSource>>> {
+    log::trace!("sources::create_sources()");
+
+    let mut sources: Vec<Box<dyn ReleaseSource>> = Vec::new();
+
+    for name in names {
+        let source: Box<dyn ReleaseSource> = match name.trim().to_lowercase().as_str() {
+            "weltbild" => Box::new(Weltbild::create(search, today)),
+            "thalia" => Box::new(Thalia::create(search, today)),
+            "hugendubel" => Box::new(Hugendubel::create(search, today)),
+            _ => anyhow::bail!("Unknown release source '{}'", name),
+        };
+        sources.push(source);
+    }
+
+    if sources.is_empty() {
+        anyhow::bail!("No release source given!");
+    }
+
+    Ok(sources)
+}
+
+/// CSS selectors of the fields of a result tile, relative to the tile.
+pub(crate) struct TileSelectors {
+    pub title: &'static str,
+    pub author: &'static str,
+    pub format: &'static str,
+    pub price: &'static str,
+    pub publisher: &'static str,
+    pub date: &'static str,
+}
+
+/// Custom data type to collect the raw text of the fields of a result tile.
+struct TileFields {
+    title: String,
+    author: String,
+    format: String,
+    price: String,
+    publisher: String,
+    date: String,
+}
+
+/// Parse the result tiles of a search result page. The fields of a tile are extracted via the
+/// given CSS selectors. If no selectors are given or the tile does not match them, the tile is
+/// flattened to its text lines and checked for the author, the title, the book format and the
+/// release date instead. Series and volume are split off the title. The ISBN is searched in the
+/// whole tile, the product page and the cover image are taken from its first link and image.
+///
+/// # Arguments
+///
+/// html_content - The HTML content of the search result page
+/// tile_selector - CSS selector matching one search result
+/// selectors - CSS selectors of the fields of a search result, if known
+/// base_url - The URL of the webpage, used to resolve relative links to the product pages
+/// author - The author to search for, listed as <surname, forename>
+/// formats - Accepted book formats. The format detected in the tile is recorded on the release,
+/// tiles of other formats are skipped
+/// today - The current date. Tiles released before it are skipped
+///
+/// # Return
+///
+/// A list of upcoming releases. Could be empty for no upcoming release
+pub(crate) fn parse_tiles(
+    html_content: &str,
+    tile_selector: &str,
+    selectors: Option<&TileSelectors>,
+    base_url: &str,
+    author: &str,
+    formats: &[MediaFormat],
+    today: chrono::NaiveDate,
+) -> Vec<UpcomingRelease> {
+    log::trace!("sources::parse_tiles()");
+
+    let mut upcoming_releases: Vec<UpcomingRelease> = Vec::new();
+
+    // rearrange the author name to search in the formatted content
+    let formatted_author = match format::format_author_name(author) {
+        Ok(rearranged) => rearranged,
+        Err(err) => {
+            log::trace!(
+                "Failed to get formatted author name for '{}': {}",
+                &author,
+                err
+            );
+            return upcoming_releases;
+        }
+    };
+
+    // Parse the HTML content
+    let document = scraper::Html::parse_document(html_content);
+    let selector = match scraper::Selector::parse(tile_selector) {
+        Ok(selector) => selector,
+        Err(err) => {
+            log::error!("Invalid selector '{}': {}", tile_selector, err);
+            return upcoming_releases;
+        }
+    };
+
+    for tile in document.select(&selector) {
+        // prefer the dedicated fields, the text lines are only a fallback
+        let fields = match selectors.and_then(|selectors| select_fields(&tile, selectors)) {
+            Some(fields) => fields,
+            None => match text_fields(&tile, &formatted_author, selectors) {
+                Some(fields) => fields,
+                None => continue,
+            },
+        };
+
+        // if the author is no contributor or the format is not accepted, no upcoming release is
+        // available
+        let contributors = format::split_contributors(&fields.author);
+        if !format::is_contributor(&contributors, &formatted_author) {
+            continue;
+        }
+        let Some(book_format) = format::detect_media_format(&fields.format) else {
+            log::debug!("Skipping '{}', no known book format", &fields.title);
+            continue;
+        };
+        if !formats.contains(&book_format) {
+            log::debug!(
+                "Skipping '{}', the format {} is not accepted",
+                &fields.title,
+                book_format
+            );
+            continue;
+        }
+
+        let formatted_date = match format::format_release_date(&fields.date) {
+            Ok(date) => date,
+            Err(err) => {
+                log::trace!("Failed to get formatted date for '{}': {}", &author, err);
+                continue;
+            }
+        };
+        if formatted_date.date_naive() < today {
+            log::debug!("Skipping '{}', it is already released", &fields.title);
+            continue;
+        }
+
+        log::info!(
+            "Upcoming release '{}' for '{}' available!",
+            &fields.title,
+            &formatted_author
+        );
+        // the whole tile is checked for signs of a reissue, e.g. the year of first publication,
+        // and for the ISBN
+        let tile_text = tile.text().collect::<Vec<_>>().join(" ");
+        let reissue = reissues::detect_reissue(&tile_text, formatted_date.year());
+        let isbn = format::find_isbn(&tile_text);
+
+        // the series and its volume are split off the title, e.g. "Knochenkälte / David Hunter Bd.7"
+        let series_title = series::parse_series(&fields.title);
+
+        let mut upcoming_release =
+            UpcomingRelease::create(formatted_author.clone(), series_title.title, formatted_date);
+        upcoming_release.contributors = contributors;
+        upcoming_release.url = product_url(&tile, base_url).unwrap_or_default();
+        upcoming_release.cover_url = cover_url(&tile, base_url).unwrap_or_default();
+        upcoming_release.format = book_format.to_string();
+        upcoming_release.price = fields.price;
+        upcoming_release.publisher = fields.publisher;
+        upcoming_release.isbn = isbn.unwrap_or_default();
+        upcoming_release.series = series_title.series;
+        upcoming_release.volume = series_title.volume;
+        upcoming_release.reissue = reissue.unwrap_or_default();
+        upcoming_releases.push(upcoming_release);
+    }
+
+    upcoming_releases
+}
+
+/// Extract the fields of a result tile via CSS selectors.
+///
+/// # Arguments
+///
+/// tile - The result tile
+/// selectors - CSS selectors of the fields
+///
+/// # Return
+///
+/// Some(TileFields) - The fields of the tile. Price and publisher are empty if not found
+/// None - The title, author, format or release date is not found
+fn select_fields(tile: &scraper::ElementRef, selectors: &TileSelectors) -> Option<TileFields> {
+    log::trace!("sources::select_fields()");
+
+    let fields = TileFields {
+        title: select_text(tile, selectors.title)?,
+        author: select_text(tile, selectors.author)?,
+        format: select_text(tile, selectors.format)?,
+        price: select_text(tile, selectors.price).unwrap_or_default(),
+        publisher: select_text(tile, selectors.publisher).unwrap_or_default(),
+        date: select_text(tile, selectors.date)?,
+    };
+
+    if fields.title.is_empty() {
+        return None;
+    }
+
+    Some(fields)
+}
+
+/// Extract the fields of a result tile from its text lines. The title is the line before the
+/// author, the release date is searched in all lines. The publisher is taken from a line like
+/// "Verlag: Heyne". The format is taken from its dedicated element if given, else from a short
+/// line naming a format, e.g. "Taschenbuch". Only if there is none, all lines are searched.
+///
+/// # Arguments
+///
+/// tile - The result tile
+/// formatted_author - The author, listed as <forename surname>
+/// selectors - CSS selectors of the fields of a search result, if known
+///
+/// # Return
+///
+/// Some(TileFields) - The fields of the tile. The author is the line of the author, the price is
+/// always empty and the publisher is empty if not found
+/// None - The author or the title is not found
+fn text_fields(
+    tile: &scraper::ElementRef,
+    formatted_author: &str,
+    selectors: Option<&TileSelectors>,
+) -> Option<TileFields> {
+    log::trace!("sources::text_fields()");
+
+    // remove trailing whitespaces and blank lines from string
+    let raw_content = tile.text().collect::<String>();
+    let formatted_content = raw_content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.trim())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    log::trace!(
+        "Formatted HTML content for '{}':\n{:?}",
+        formatted_author,
+        &formatted_content
+    );
+
+    // the line of the author lists all contributors
+    let author_line = formatted_content
+        .lines()
+        .find(|line| line.contains(formatted_author))?
+        .to_string();
+
+    let title = match format::format_release_title(&formatted_content, formatted_author) {
+        Ok(title) => title,
+        Err(err) => {
+            log::trace!(
+                "Failed to get formatted release title for '{}': {}",
+                formatted_author,
+                err
+            );
+            return None;
+        }
+    };
+
+    let publisher = formatted_content
+        .lines()
+        .find_map(|line| line.strip_prefix(PUBLISHER_PREFIX))
+        .map(|publisher| publisher.trim().to_string())
+        .unwrap_or_default();
+
+    // a blurb may mention other formats, e.g. "Auch als Hörbuch erhältlich"
+    let format = selectors
+        .and_then(|selectors| select_text(tile, selectors.format))
+        .or_else(|| {
+            formatted_content
+                .lines()
+                .filter(|line| *line != author_line && *line != title)
+                .filter(|line| !line.starts_with(PUBLISHER_PREFIX))
+                .filter(|line| line.split_whitespace().count() <= FORMAT_LINE_WORDS)
+                .find(|line| format::detect_media_format(line).is_some())
+                .map(|line| line.to_string())
+        })
+        .unwrap_or_else(|| formatted_content.clone());
+
+    Some(TileFields {
+        title,
+        author: author_line,
+        format,
+        price: String::new(),
+        publisher,
+        date: formatted_content,
+    })
+}
+
+/// Get the text of the first element matching a CSS selector. Every sequence of whitespaces is
+/// reduced to a single space, so wrapped titles end up on one line.
+///
+/// # Arguments
+///
+/// tile - The result tile
+/// selector - CSS selector of the element, relative to the tile
+///
+/// # Return
+///
+/// Some(String) - The text of the element
+/// None - No element matches the selector
+fn select_text(tile: &scraper::ElementRef, selector: &str) -> Option<String> {
+    let selector = scraper::Selector::parse(selector).ok()?;
+    let element = tile.select(&selector).next()?;
+
+    Some(
+        element
+            .text()
+            .collect::<String>()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
+/// Get the URL of the product page a result tile links to.
+///
+/// # Arguments
+///
+/// tile - The result tile
+/// base_url - The URL of the webpage, used to resolve relative links
+///
+/// # Return
+///
+/// Some(String) - The absolute URL of the product page
+/// None - The tile contains no link
+pub(crate) fn product_url(tile: &scraper::ElementRef, base_url: &str) -> Option<String> {
+    let link_selector = scraper::Selector::parse("a[href]").unwrap();
+    let href = tile.select(&link_selector).next()?.value().attr("href")?;
+
+    let url = reqwest::Url::parse(base_url).ok()?.join(href).ok()?;
+    Some(url.to_string())
+}
+
+/// Get the URL of the cover image of a result tile. Lazy loaded images keep their URL in
+/// "data-src" until they are shown, so it is preferred over "src".
+///
+/// # Arguments
+///
+/// tile - The result tile
+/// base_url - The URL of the webpage, used to resolve relative links
+///
+/// # Return
+///
+/// Some(String) - The absolute URL of the cover image
+/// None - The tile contains no image
+pub(crate) fn cover_url(tile: &scraper::ElementRef, base_url: &str) -> Option<String> {
+    let image_selector = scraper::Selector::parse("img").unwrap();
+    let image = tile.select(&image_selector).next()?.value();
+    let src = image.attr("data-src").or_else(|| image.attr("src"))?;
+
+    let url = reqwest::Url::parse(base_url).ok()?.join(src).ok()?;
+    Some(url.to_string())
+}
+
+/// Get the URL of the next search result page a search result page links to.
+///
+/// # Arguments
+///
+/// html_content - The HTML content of the search result page
+/// selector - CSS selector of the link to the next page
+/// base_url - The URL of the webpage, used to resolve relative links
+///
+/// # Return
+///
+/// Some(String) - The absolute URL of the next page
+/// None - There is no next page
+pub(crate) fn next_page_url(html_content: &str, selector: &str, base_url: &str) -> Option<String> {
+    log::trace!("sources::next_page_url()");
+
+    let document = scraper::Html::parse_document(html_content);
+    let selector = scraper::Selector::parse(selector).ok()?;
+    let href = document.select(&selector).next()?.value().attr("href")?;
+
+    let url = reqwest::Url::parse(base_url).ok()?.join(href).ok()?;
+    Some(url.to_string())
+}
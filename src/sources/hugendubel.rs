@@ -0,0 +1,73 @@
+//! This module implements Hugendubel.de as source for upcoming releases.
+
+use crate::config::SearchConfig;
+use crate::customtypes::{Author, UpcomingRelease};
+use crate::format;
+use crate::sources::{self, ReleaseSource};
+use anyhow::Result;
+
+const HUGENDUBEL_URL: &str = "https://www.hugendubel.de";
+const SEARCH: &str = "/de/search?q=";
+const TYPE: &str = "&productType=buch";
+const ORDER: &str = "&sort=erscheinungsdatum-desc";
+const TILE: &str = "div.product-tile";
+
+/// Scrape the search results of Hugendubel.de.
+pub struct Hugendubel {
+    search: SearchConfig,
+    today: chrono::NaiveDate,
+}
+
+impl Hugendubel {
+    pub fn create(search: &SearchConfig, today: chrono::NaiveDate) -> Self {
+        Hugendubel {
+            search: search.clone(),
+            today,
+        }
+    }
+}
+
+impl ReleaseSource for Hugendubel {
+    fn name(&self) -> &'static str {
+        "Hugendubel"
+    }
+
+    fn search_config(&self) -> &SearchConfig {
+        &self.search
+    }
+
+    /// Build the Hugendubel URL to search for the books of an author, sorted by release date (newest
+    /// first).
+    fn search_url(&self, author: &Author, _language: &str) -> Result<String> {
+        log::trace!("hugendubel::search_url()");
+
+        // Hugendubel expects the author in the format <forename surname>
+        let updated_author = format::format_author_name(&author.name)?.replace(' ', "+");
+
+        // the search is only restricted to books if no other media type is accepted
+        let media_type = if self.search.for_author(author).only_printed() {
+            TYPE
+        } else {
+            ""
+        };
+
+        Ok(HUGENDUBEL_URL.to_owned() + SEARCH + &updated_author + media_type + ORDER)
+    }
+
+    /// Parse the html contents to get potential upcoming releases of an author.
+    fn parse(&self, html_content: &str, author: &Author) -> Vec<UpcomingRelease> {
+        log::trace!("hugendubel::parse()");
+
+        let search = self.search.for_author(author);
+
+        sources::parse_tiles(
+            html_content,
+            TILE,
+            None,
+            HUGENDUBEL_URL,
+            &author.name,
+            &search.formats,
+            self.today,
+        )
+    }
+}
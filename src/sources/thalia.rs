@@ -0,0 +1,73 @@
+//! This module implements Thalia.de as source for upcoming releases.
+
+use crate::config::SearchConfig;
+use crate::customtypes::{Author, UpcomingRelease};
+use crate::format;
+use crate::sources::{self, ReleaseSource};
+use anyhow::Result;
+
+const THALIA_URL: &str = "https://www.thalia.de";
+const SEARCH: &str = "/suche?sq=";
+const TYPE: &str = "&filterPATHROOT=buch";
+const ORDER: &str = "&sort=sfed";
+const TILE: &str = "li.tm-produktliste__eintrag";
+
+/// Scrape the search results of Thalia.de.
+pub struct Thalia {
+    search: SearchConfig,
+    today: chrono::NaiveDate,
+}
+
+impl Thalia {
+    pub fn create(search: &SearchConfig, today: chrono::NaiveDate) -> Self {
+        Thalia {
+            search: search.clone(),
+            today,
+        }
+    }
+}
+
+impl ReleaseSource for Thalia {
+    fn name(&self) -> &'static str {
+        "Thalia"
+    }
+
+    fn search_config(&self) -> &SearchConfig {
+        &self.search
+    }
+
+    /// Build the Thalia URL to search for the books of an author, sorted by release date (newest
+    /// first).
+    fn search_url(&self, author: &Author, _language: &str) -> Result<String> {
+        log::trace!("thalia::search_url()");
+
+        // Thalia expects the author in the format <forename surname>
+        let updated_author = format::format_author_name(&author.name)?.replace(' ', "+");
+
+        // the search is only restricted to books if no other media type is accepted
+        let media_type = if self.search.for_author(author).only_printed() {
+            TYPE
+        } else {
+            ""
+        };
+
+        Ok(THALIA_URL.to_owned() + SEARCH + &updated_author + media_type + ORDER)
+    }
+
+    /// Parse the html contents to get potential upcoming releases of an author.
+    fn parse(&self, html_content: &str, author: &Author) -> Vec<UpcomingRelease> {
+        log::trace!("thalia::parse()");
+
+        let search = self.search.for_author(author);
+
+        sources::parse_tiles(
+            html_content,
+            TILE,
+            None,
+            THALIA_URL,
+            &author.name,
+            &search.formats,
+            self.today,
+        )
+    }
+}
@@ -0,0 +1,115 @@
+//! This module implements Weltbild.de as source for upcoming releases.
+
+use crate::config::SearchConfig;
+use crate::customtypes::{Author, UpcomingRelease};
+use crate::sources::{self, ReleaseSource, TileSelectors};
+use anyhow::Result;
+
+const WELTBILD_URL: &str = "https://www.weltbild.de";
+const SEARCH: &str = "/suche/";
+const RELEASE_YEAR: &str = "jahr";
+const TYPE: &str = "node";
+const LANGUAGE: &str = "sprache";
+const TILE: &str = "div.inner-flex-container";
+const NEXT_PAGE: &str = "a[rel=\"next\"]";
+const FIELDS: TileSelectors = TileSelectors {
+    title: "a.product-title",
+    author: "div.product-author",
+    format: "div.product-format",
+    price: "div.product-price",
+    publisher: "div.product-publisher",
+    date: "div.product-availability",
+};
+
+/// Scrape the search results of Weltbild.de.
+pub struct Weltbild {
+    search: SearchConfig,
+    today: chrono::NaiveDate,
+}
+
+impl Weltbild {
+    pub fn create(search: &SearchConfig, today: chrono::NaiveDate) -> Self {
+        Weltbild {
+            search: search.clone(),
+            today,
+        }
+    }
+}
+
+impl ReleaseSource for Weltbild {
+    fn name(&self) -> &'static str {
+        "Weltbild"
+    }
+
+    fn search_config(&self) -> &SearchConfig {
+        &self.search
+    }
+
+    /// Search every configured language separately, or all languages at once if none is
+    /// configured.
+    fn languages(&self, author: &Author) -> Vec<String> {
+        let languages = self.search.for_author(author).languages;
+
+        if languages.is_empty() {
+            vec![String::new()]
+        } else {
+            languages
+        }
+    }
+
+    /// Build the Weltbild URL to search for the books of an author.
+    fn search_url(&self, author: &Author, language: &str) -> Result<String> {
+        log::trace!("weltbild::search_url()");
+
+        let search = self.search.for_author(author);
+
+        // WELTBILD_URL: The URL to Weltbild.de
+        // SEARCH: Initiate a search
+        // RELEASE_YEAR: The release year, "0" means upcoming releases
+        // TYPE: The media type, e.g. books, no audibles or something similar
+        // LANGUAGE: The language of the books. Omitted if empty
+        let updated_author = author.name.replace(", ", "+");
+
+        let query = [
+            (RELEASE_YEAR, search.release_year.as_str()),
+            (TYPE, search.media_type().as_str()),
+            (LANGUAGE, language),
+        ]
+        .iter()
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(key, value)| format!("{}={}", key, value.replace('/', "%2F")))
+        .collect::<Vec<_>>()
+        .join("&");
+
+        let mut url = WELTBILD_URL.to_owned() + SEARCH + &updated_author;
+        if !query.is_empty() {
+            url = url + "?" + &query;
+        }
+
+        Ok(url)
+    }
+
+    /// Parse the html contents to get potential upcoming releases of an author.
+    fn parse(&self, html_content: &str, author: &Author) -> Vec<UpcomingRelease> {
+        log::trace!("weltbild::parse()");
+
+        let search = self.search.for_author(author);
+
+        sources::parse_tiles(
+            html_content,
+            TILE,
+            Some(&FIELDS),
+            WELTBILD_URL,
+            &author.name,
+            &search.formats,
+            self.today,
+        )
+    }
+
+    /// Follow the link to the next search result page.
+    fn next_page(&self, html_content: &str) -> Option<String> {
+        log::trace!("weltbild::next_page()");
+
+        sources::next_page_url(html_content, NEXT_PAGE, WELTBILD_URL)
+    }
+}
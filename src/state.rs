@@ -0,0 +1,313 @@
+//! This module persists the releases of a run and compares them with the releases of the previous
+//! run to report new and changed releases.
+
+use crate::customtypes::{ChangeKind, ReleaseChange, UpcomingRelease};
+use crate::export;
+use crate::format;
+use crate::series;
+use anyhow::{Context, Result};
+use std::fmt::Write;
+
+/// Custom data type of the state file. It is the JSON document of the JSON export.
+#[derive(serde::Deserialize)]
+struct StateFile {
+    schema_version: u32,
+    releases: Vec<UpcomingRelease>,
+}
+
+/// Schema version of the state files written before the book format was detected and the series
+/// was split off the title. These state files are migrated while loading.
+const SCHEMA_VERSION_1: u32 = 1;
+
+/// Load the releases of the previous run from the state file. A state file of schema version 1 is
+/// migrated to the current schema version.
+///
+/// # Arguments
+///
+/// path - Path to the state file
+///
+/// # Return
+///
+/// Ok(Vec<UpcomingRelease>) - The releases of the previous run. Empty if there is no state file yet
+/// Err(err) - Some error occured
+pub fn load_state(path: &str) -> Result<Vec<UpcomingRelease>> {
+    log::trace!("state::load_state()");
+
+    // no state file means there was no previous run
+    if !std::path::Path::new(path).exists() {
+        log::info!("State file '{}' not found, all releases are new", path);
+        return Ok(Vec::new());
+    }
+
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read state file '{}'", path))?;
+    let state: StateFile = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse state file '{}'", path))?;
+
+    let releases = match state.schema_version {
+        export::SCHEMA_VERSION => state.releases,
+        SCHEMA_VERSION_1 => {
+            log::info!(
+                "Migrating state file '{}' from schema version {} to {}",
+                path,
+                SCHEMA_VERSION_1,
+                export::SCHEMA_VERSION
+            );
+            state.releases.into_iter().map(migrate_release).collect()
+        }
+        _ => anyhow::bail!(
+            "State file '{}' has schema version {}, expected {}",
+            path,
+            state.schema_version,
+            export::SCHEMA_VERSION
+        ),
+    };
+
+    log::debug!("Loaded {} releases from '{}'", releases.len(), path);
+
+    Ok(releases)
+}
+
+/// Migrate a release of schema version 1 to the current schema version: the format as listed on
+/// the release source (e.g. "Buch") is replaced by the detected book format and the series is split
+/// off the title.
+fn migrate_release(mut release: UpcomingRelease) -> UpcomingRelease {
+    if let Some(book_format) = format::detect_media_format(&release.format) {
+        release.format = book_format.to_string();
+    }
+
+    if release.series.is_empty() {
+        let series_title = series::parse_series(&release.title);
+        release.title = series_title.title;
+        release.series = series_title.series;
+        release.volume = series_title.volume;
+    }
+
+    release
+}
+
+/// Save the releases of this run to the state file.
+///
+/// # Arguments
+///
+/// path - Path to the state file
+/// releases - The releases to save
+///
+/// # Return
+///
+/// Ok() - Successfully saved the releases
+/// Err(err) - Some error occured
+pub fn save_state(path: &str, releases: &[UpcomingRelease]) -> Result<()> {
+    log::trace!("state::save_state()");
+
+    if let Some(parent) = std::path::Path::new(path).parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory of state file '{}'", path))?;
+    }
+
+    std::fs::write(path, export::to_json(releases)?)
+        .with_context(|| format!("Failed to write state file '{}'", path))?;
+
+    log::debug!("Saved {} releases to '{}'", releases.len(), path);
+
+    Ok(())
+}
+
+/// Compare the releases of this run with the releases of the previous run. A release is identified
+/// by the URL of its product page. If there is no URL, it is identified by author and title.
+/// Releases of the previous run dated before today are published by now, so they are not reported
+/// as disappeared.
+///
+/// # Arguments
+///
+/// previous - The releases of the previous run
+/// current - The releases of this run
+/// unchecked_authors - Authors (rearranged to <forename surname>) which could not be checked in
+/// this run. Their releases are never reported as disappeared
+/// today - The current date
+///
+/// # Return
+///
+/// The new, changed and disappeared releases. Unchanged releases are not part of the list
+pub fn compare(
+    previous: &[UpcomingRelease],
+    current: &[UpcomingRelease],
+    unchecked_authors: &[String],
+    today: chrono::NaiveDate,
+) -> Vec<ReleaseChange> {
+    log::trace!("state::compare()");
+
+    let mut changes = Vec::new();
+    let mut matched = vec![false; previous.len()];
+
+    for release in current {
+        let position = previous
+            .iter()
+            .enumerate()
+            .position(|(index, known)| !matched[index] && is_same_release(known, release));
+
+        let kind = match position {
+            None => ChangeKind::New,
+            Some(index) => {
+                matched[index] = true;
+                let known = &previous[index];
+
+                if known.title != release.title {
+                    ChangeKind::TitleChanged(known.title.clone())
+                } else if known.date.date_naive() != release.date.date_naive() {
+                    ChangeKind::DateChanged(known.date)
+                } else {
+                    continue;
+                }
+            }
+        };
+
+        changes.push(ReleaseChange {
+            kind,
+            release: release.clone(),
+        });
+    }
+
+    for (known, _) in previous.iter().zip(matched).filter(|(_, matched)| !matched) {
+        // released books are no longer found as upcoming releases
+        if unchecked_authors.contains(&known.author) || known.date.date_naive() < today {
+            continue;
+        }
+
+        changes.push(ReleaseChange {
+            kind: ChangeKind::Disappeared,
+            release: known.clone(),
+        });
+    }
+
+    log::info!("{} new, changed or disappeared releases", changes.len());
+
+    changes
+}
+
+/// Get the releases to save as state of this run. The upcoming releases of authors which could not
+/// be checked are taken over from the previous run, unless they were found in this run anyway (e.g.
+/// via a pseudonym of the author).
+///
+/// # Arguments
+///
+/// previous - The releases of the previous run
+/// current - The releases of this run
+/// unchecked_authors - Authors (rearranged to <forename surname>) which could not be checked
+/// today - The current date. Releases of the previous run before it are dropped
+///
+/// # Return
+///
+/// The releases to save
+pub fn next_state(
+    previous: &[UpcomingRelease],
+    current: &[UpcomingRelease],
+    unchecked_authors: &[String],
+    today: chrono::NaiveDate,
+) -> Vec<UpcomingRelease> {
+    log::trace!("state::next_state()");
+
+    let mut releases = current.to_vec();
+    releases.extend(
+        previous
+            .iter()
+            .filter(|known| unchecked_authors.contains(&known.author))
+            .filter(|known| known.date.date_naive() >= today)
+            .filter(|known| {
+                !current
+                    .iter()
+                    .any(|release| is_same_release(known, release))
+            })
+            .cloned(),
+    );
+
+    releases
+}
+
+/// Write the report of the changes to a file.
+///
+/// # Arguments
+///
+/// changes - The changed releases
+/// path - Path to the report file
+///
+/// # Return
+///
+/// Ok() - Successfully created the report file
+/// Err(err) - Some error occured
+pub fn create_report(changes: &[ReleaseChange], path: &str) -> Result<()> {
+    log::trace!("state::create_report()");
+
+    std::fs::write(path, render_report(changes)?)
+        .with_context(|| format!("Failed to write report file '{}'", path))?;
+
+    log::info!("Successfully created report file '{}'", path);
+
+    Ok(())
+}
+
+/// Render the changes as human-readable report, one change per line.
+///
+/// # Arguments
+///
+/// changes - The changed releases
+///
+/// # Return
+///
+/// Ok(String) - The report
+/// Err(err) - Some error occured
+pub fn render_report(changes: &[ReleaseChange]) -> Result<String> {
+    log::trace!("state::render_report()");
+
+    let mut report = String::new();
+
+    writeln!(report, "Changes since last run")?;
+    writeln!(report)?;
+
+    if changes.is_empty() {
+        writeln!(report, "No new or changed releases")?;
+    }
+
+    for change in changes {
+        let release = &change.release;
+        let date = release.date.format("%Y-%m-%d");
+
+        match &change.kind {
+            ChangeKind::New => writeln!(
+                report,
+                "[new] {} - \"{}\" ({})",
+                release.author, release.title, date
+            )?,
+            ChangeKind::DateChanged(previous_date) => writeln!(
+                report,
+                "[date changed] {} - \"{}\" ({} -> {})",
+                release.author,
+                release.title,
+                previous_date.format("%Y-%m-%d"),
+                date
+            )?,
+            ChangeKind::TitleChanged(previous_title) => writeln!(
+                report,
+                "[title changed] {} - \"{}\" -> \"{}\" ({})",
+                release.author, previous_title, release.title, date
+            )?,
+            ChangeKind::Disappeared => writeln!(
+                report,
+                "[disappeared] {} - \"{}\" ({})",
+                release.author, release.title, date
+            )?,
+        }
+    }
+
+    Ok(report)
+}
+
+/// Check whether two releases are the same release, maybe with changed title or release date.
+fn is_same_release(known: &UpcomingRelease, release: &UpcomingRelease) -> bool {
+    if !known.url.is_empty() && !release.url.is_empty() {
+        return known.url == release.url;
+    }
+
+    known.author == release.author
+        && format::normalize_title(&known.title) == format::normalize_title(&release.title)
+}
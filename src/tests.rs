@@ -1,11 +1,47 @@
 #[cfg(test)]
 mod authors_tests;
 
+#[cfg(test)]
+mod cli_tests;
+
+#[cfg(test)]
+mod config_tests;
+
+#[cfg(test)]
+mod details_tests;
+
+#[cfg(test)]
+mod editions_tests;
+
+#[cfg(test)]
+mod export_tests;
+
+#[cfg(test)]
+mod fetcher_tests;
+
 #[cfg(test)]
 mod format_tests;
 
+#[cfg(test)]
+mod main_tests;
+
+#[cfg(test)]
+mod ratelimit_tests;
+
+#[cfg(test)]
+mod reissues_tests;
+
 #[cfg(test)]
 mod releases_tests;
 
 #[cfg(test)]
 mod scraper_tests;
+
+#[cfg(test)]
+mod series_tests;
+
+#[cfg(test)]
+mod sources_tests;
+
+#[cfg(test)]
+mod state_tests;
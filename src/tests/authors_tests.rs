@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
     use crate::authors;
+    use crate::customtypes::{Author, MediaFormat};
     use crate::logger;
     use serial_test::serial;
     use std::io::Write;
@@ -23,7 +24,6 @@ mod tests {
 
         let mut authors_file = std::fs::OpenOptions::new()
             .create(true)
-            .write(true)
             .append(true)
             .open(AUTHORS_FILE)
             .expect("Cannot open file");
@@ -34,10 +34,7 @@ mod tests {
         assert!(std::path::Path::new(AUTHORS_FILE).exists());
 
         // now get the authors
-        let authors = match authors::read_authors(AUTHORS_FILE) {
-            Ok(extracted_authors) => extracted_authors,
-            Err(_) => Vec::new(),
-        };
+        let authors = authors::read_authors(AUTHORS_FILE).unwrap_or_default();
         assert!(!authors.is_empty());
 
         let number_of_lines =
@@ -46,11 +43,11 @@ mod tests {
         assert_eq!(authors.len(), number_of_lines);
 
         // exemplary data to compare with
-        let author_1 = AUTHOR_1.into();
-        let author_2 = AUTHOR_2.into();
-        let author_3 = AUTHOR_3.into();
+        let author_1 = Author::create(AUTHOR_1.into());
+        let author_2 = Author::create(AUTHOR_2.into());
+        let author_3 = Author::create(AUTHOR_3.into());
 
-        let authors_iter: Vec<_> = authors.into_iter().map(String::from).collect();
+        let authors_iter: Vec<_> = authors.into_iter().collect();
 
         assert!(authors_iter.contains(&author_1));
         assert!(authors_iter.contains(&author_2));
@@ -61,6 +58,181 @@ mod tests {
         assert!(!std::path::Path::new(AUTHORS_FILE).exists());
     }
 
+    #[test]
+    #[serial]
+    fn test_read_authors_with_options() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let contents = "# Horror\n\
+            \n\
+            King, Stephen | tag=horror   # the king\n\
+            Bachman, Richard = King, Stephen | tag=horror\n\
+            \u{20}  Follett, Ken | languages=/language/eng, /language/ger | formats=Taschenbuch, Buch\n";
+        std::fs::write(AUTHORS_FILE, contents).expect("Failed to write file");
+
+        let authors = authors::read_authors(AUTHORS_FILE).unwrap();
+        assert_eq!(authors.len(), 3);
+
+        assert_eq!(authors[0].name, AUTHOR_3);
+        assert_eq!(authors[0].tag.as_deref(), Some("horror"));
+        assert_eq!(authors[0].release_author(), AUTHOR_3);
+
+        // the releases of a pseudonym are reported for the author behind it
+        assert_eq!(authors[1].name, "Bachman, Richard");
+        assert_eq!(authors[1].alias_of.as_deref(), Some(AUTHOR_3));
+        assert_eq!(authors[1].release_author(), AUTHOR_3);
+
+        assert_eq!(authors[2].name, "Follett, Ken");
+        assert_eq!(
+            authors[2].languages,
+            Some(vec![
+                "/language/eng".to_string(),
+                "/language/ger".to_string()
+            ])
+        );
+        assert_eq!(
+            authors[2].formats,
+            Some(vec![MediaFormat::Taschenbuch, MediaFormat::Hardcover])
+        );
+        assert_eq!(authors[2].tag, None);
+
+        // cleanup
+        let _ = std::fs::remove_file(AUTHORS_FILE);
+    }
+
+    #[test]
+    #[serial]
+    fn test_read_authors_normalized() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        // byte order mark and whitespaces are ignored, duplicates are skipped
+        let contents = "\u{feff}King, Stephen\n  Brown,   Dan  \nking, stéphen\nBROWN, DAN\n";
+        std::fs::write(AUTHORS_FILE, contents).expect("Failed to write file");
+
+        let authors = authors::read_authors(AUTHORS_FILE).unwrap();
+        assert_eq!(
+            authors,
+            vec![
+                Author::create(AUTHOR_3.to_string()),
+                Author::create(AUTHOR_1.to_string())
+            ]
+        );
+
+        // cleanup
+        let _ = std::fs::remove_file(AUTHORS_FILE);
+    }
+
+    #[test]
+    #[serial]
+    fn test_check_authors() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        assert!(authors::check_authors("# Thriller\nBrown, Dan\n\nKing, Stephen\n").is_empty());
+
+        let issues = authors::check_authors(
+            "\u{feff}King, Stephen\nBrown, Dan \nDan Brown\nKing, Stephen, Jr\nKING, Stéphen\n",
+        );
+        assert_eq!(issues.len(), 5);
+        assert!(issues[0].starts_with("line 1: Byte order mark"));
+        assert!(issues[1].starts_with("line 2: Leading or trailing whitespaces"));
+        assert!(issues[2].starts_with("line 3: 'Dan Brown' is not listed"));
+        assert!(issues[3].starts_with("line 4: 'King, Stephen, Jr' is not listed"));
+        assert_eq!(
+            issues[4],
+            "line 5: 'KING, Stéphen' is a duplicate of line 1"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_fix_authors() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let contents = "\u{feff}# Horror\n\
+            King, Stephen|tag=horror  \n\
+            \n\
+            \u{20}Cross,  Ethan # Der Hundemacher\n\
+            Bachman, Richard=King, Stephen\n\
+            king, stéphen\n\
+            # end of list\n";
+
+        let fixed = authors::fix_authors(contents).unwrap();
+        assert_eq!(
+            fixed,
+            "Bachman, Richard = King, Stephen\n\
+             Cross, Ethan # Der Hundemacher\n\
+             # Horror\n\
+             King, Stephen | tag=horror\n\
+             # end of list\n"
+        );
+
+        // the fixed list is valid and stays the same when fixed again
+        assert!(authors::check_authors(&fixed).is_empty());
+        assert_eq!(authors::fix_authors(&fixed).unwrap(), fixed);
+
+        // malformed lines can not be fixed
+        assert!(authors::fix_authors("King, Stephen\nDan Brown\n").is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_validate_authors() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        std::fs::write(AUTHORS_FILE, "King, Stephen\nBrown, Dan\nbrown, dan\n")
+            .expect("Failed to write file");
+
+        // issues are an error unless they are fixed
+        assert!(authors::validate_authors(AUTHORS_FILE, false).is_err());
+        assert!(authors::validate_authors(AUTHORS_FILE, true).is_ok());
+        assert_eq!(
+            std::fs::read_to_string(AUTHORS_FILE).unwrap(),
+            "Brown, Dan\nKing, Stephen\n"
+        );
+        assert!(authors::validate_authors(AUTHORS_FILE, false).is_ok());
+
+        // test non-existing file
+        assert!(authors::validate_authors(NON_EXISTING_FILE, true).is_err());
+
+        // cleanup
+        let _ = std::fs::remove_file(AUTHORS_FILE);
+    }
+
+    #[test]
+    #[serial]
+    fn test_add_and_remove_author() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        // the file is created with the first author
+        let _ = std::fs::remove_file(AUTHORS_FILE);
+        authors::add_author(AUTHORS_FILE, "King,   Stephen|tag=horror").unwrap();
+
+        // a missing line break at the end of the file is added
+        std::fs::write(AUTHORS_FILE, "# Thriller\nKing, Stephen | tag=horror").unwrap();
+        authors::add_author(AUTHORS_FILE, AUTHOR_1).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(AUTHORS_FILE).unwrap(),
+            "# Thriller\nKing, Stephen | tag=horror\nBrown, Dan\n"
+        );
+
+        // authors are listed only once
+        assert!(authors::add_author(AUTHORS_FILE, "brown, dän").is_err());
+        assert!(authors::add_author(AUTHORS_FILE, "Dan Brown").is_err());
+        assert!(authors::add_author(AUTHORS_FILE, "# Brown, Dan").is_err());
+
+        // every other line is kept when an author is removed
+        authors::remove_author(AUTHORS_FILE, "KING, Stephen").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(AUTHORS_FILE).unwrap(),
+            "# Thriller\nBrown, Dan\n"
+        );
+        assert!(authors::remove_author(AUTHORS_FILE, AUTHOR_3).is_err());
+        assert!(authors::remove_author(NON_EXISTING_FILE, AUTHOR_1).is_err());
+
+        // cleanup
+        let _ = std::fs::remove_file(AUTHORS_FILE);
+    }
+
     #[test]
     #[serial]
     fn test_authors_error_cases() {
@@ -75,6 +247,30 @@ mod tests {
 
         assert!(authors::read_authors(AUTHORS_FILE).is_err());
 
+        // test file with comments only
+        std::fs::write(AUTHORS_FILE, "# King, Stephen\n\n").expect("Failed to write file");
+        assert!(authors::read_authors(AUTHORS_FILE).is_err());
+
+        // test malformed line, the error names the line
+        std::fs::write(AUTHORS_FILE, "King, Stephen\n\nDan Brown\n").expect("Failed to write file");
+        let err = authors::read_authors(AUTHORS_FILE).unwrap_err();
+        assert!(format!("{:#}", err).contains("line 3"));
+
+        // test malformed names, aliases and options
+        assert!(authors::parse_author("King,Stephen").is_err());
+        assert!(authors::parse_author("Bachman, Richard = ").is_err());
+        assert!(authors::parse_author("King, Stephen | tag").is_err());
+        assert!(authors::parse_author("King, Stephen | tag=").is_err());
+        assert!(authors::parse_author("King, Stephen | genre=horror").is_err());
+        assert!(authors::parse_author("King, Stephen | tag=horror | tag=fantasy").is_err());
+        assert!(authors::parse_author("King, Stephen | formats=,").is_err());
+        assert!(authors::parse_author("King, Stephen | languages=,").is_err());
+        assert!(authors::parse_author("King, Stephen | formats=Taschenbuch,Comic").is_err());
+        assert!(authors::parse_author(
+            "King, Stephen | language=/language/eng | languages=/language/ger"
+        )
+        .is_err());
+
         // cleanup
         let _ = std::fs::remove_file(AUTHORS_FILE);
         assert!(!std::path::Path::new(AUTHORS_FILE).exists());
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use crate::cli::{Args, Command};
+    use crate::config;
+    use crate::logger;
+    use serial_test::serial;
+
+    const LOGLEVEL: &str = "Trace";
+    const OFFLINE_DIR: &str = "src/tests/fixtures";
+
+    #[test]
+    #[serial]
+    fn test_fetch_args() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        // the arguments of searching are taken no matter if given before or after "fetch"
+        for args in [
+            vec!["upcoming_book_releases", "-o", OFFLINE_DIR, "fetch"],
+            vec!["upcoming_book_releases", "fetch", "-o", OFFLINE_DIR],
+            vec!["upcoming_book_releases", "-o", OFFLINE_DIR],
+        ] {
+            let args = Args::try_parse_args(args).unwrap();
+            let fetch = args.fetch_args().unwrap();
+            assert_eq!(fetch.offline_dir.as_deref(), Some(OFFLINE_DIR));
+        }
+
+        let args = Args::try_parse_args([
+            "upcoming_book_releases",
+            "fetch",
+            "--max-results",
+            "5",
+            "--max-pages",
+            "2",
+        ])
+        .unwrap();
+        assert!(matches!(args.command, Some(Command::Fetch(_))));
+        let config = config::create_config(&args).unwrap();
+        assert_eq!(config.search.max_results, 5);
+        assert_eq!(config.search.max_pages, 2);
+
+        // but not on both sides of "fetch"
+        assert!(Args::try_parse_args([
+            "upcoming_book_releases",
+            "--max-results",
+            "5",
+            "fetch",
+            "--max-pages",
+            "2",
+        ])
+        .is_err());
+
+        // the rate of requests has to be a finite number of zero or more
+        let args = Args::try_parse_args(["upcoming_book_releases", "-q", "0.5"]).unwrap();
+        assert_eq!(args.fetch_args().unwrap().requests_per_second, 0.5);
+        for rate in ["-1", "NaN", "inf", "fast"] {
+            assert!(Args::try_parse_args(["upcoming_book_releases", "-q", rate]).is_err());
+        }
+
+        // other commands have no arguments of searching
+        let args = Args::try_parse_args(["upcoming_book_releases", "show"]).unwrap();
+        assert!(args.fetch_args().is_none());
+        for args in [
+            vec!["upcoming_book_releases", "validate", "--format", "csv"],
+            vec!["upcoming_book_releases", "authors", "list", "--details"],
+            vec![
+                "upcoming_book_releases",
+                "show",
+                "--state-file",
+                "state.json",
+            ],
+        ] {
+            assert!(Args::try_parse_args(args).is_err());
+        }
+    }
+}
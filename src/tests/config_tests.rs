@@ -0,0 +1,141 @@
+#[cfg(test)]
+mod tests {
+    use crate::cli::Args;
+    use crate::config::{self, Config, SearchConfig};
+    use crate::customtypes::MediaFormat;
+    use crate::logger;
+    use clap::Parser;
+    use serial_test::serial;
+
+    const LOGLEVEL: &str = "Trace";
+    const CONFIG_FILE: &str = "/tmp/upcoming-releases-config.toml";
+    const CONFIG_CONTENT: &str = r#"
+        authors_file = "/data/authors"
+        release_file = "books"
+
+        [search]
+        languages = []
+        formats = ["Taschenbuch"]
+        max_results = 5
+    "#;
+
+    #[test]
+    #[serial]
+    fn test_create_config() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        // no config file and no arguments result in the defaults
+        let args = Args::parse_from(["upcoming_book_releases"]);
+        assert_eq!(config::create_config(&args).unwrap(), Config::default());
+
+        // missing entries of the config file get their default value
+        std::fs::write(CONFIG_FILE, CONFIG_CONTENT).unwrap();
+        let args = Args::parse_from(["upcoming_book_releases", "--config", CONFIG_FILE]);
+        let config = config::create_config(&args).unwrap();
+
+        assert_eq!(config.authors_file, "/data/authors");
+        assert_eq!(config.dest_release, "-");
+        assert_eq!(config.release_file, "books");
+        assert_eq!(config.search.release_year, "0");
+        assert!(config.search.languages.is_empty());
+        assert_eq!(config.search.formats, vec![MediaFormat::Taschenbuch]);
+        assert_eq!(config.search.max_results, 5);
+
+        // arguments passed via command line override the config file
+        let args = Args::parse_from([
+            "upcoming_book_releases",
+            "--config",
+            CONFIG_FILE,
+            "-a",
+            "/tmp/authors",
+            "--languages",
+            "/language/eng,/language/ger",
+            "--formats",
+            "Buch,Hörbuch",
+            "--max-results",
+            "2",
+        ]);
+        let config = config::create_config(&args).unwrap();
+
+        assert_eq!(config.authors_file, "/tmp/authors");
+        assert_eq!(config.release_file, "books");
+        assert_eq!(
+            config.search,
+            SearchConfig {
+                languages: vec!["/language/eng".to_string(), "/language/ger".to_string()],
+                formats: vec![MediaFormat::Hardcover, MediaFormat::Hoerbuch],
+                max_results: 2,
+                ..SearchConfig::default()
+            }
+        );
+
+        // the search parameters can also be passed to the "fetch" command
+        let args = Args::parse_from([
+            "upcoming_book_releases",
+            "fetch",
+            "--max-results",
+            "7",
+            "-r",
+            "books.csv",
+        ]);
+        let config = config::create_config(&args).unwrap();
+        assert_eq!(config.release_file, "books.csv");
+        assert_eq!(config.search.max_results, 7);
+
+        // the media type is derived from the formats, unless it is given
+        let args = Args::parse_from(["upcoming_book_releases", "--formats", "e-book"]);
+        assert_eq!(
+            config::create_config(&args).unwrap().search.media_type(),
+            "/ebooks"
+        );
+        let args = Args::parse_from(["upcoming_book_releases", "--formats", "hardcover,hörbuch"]);
+        assert_eq!(
+            config::create_config(&args).unwrap().search.media_type(),
+            ""
+        );
+        let args = Args::parse_from([
+            "upcoming_book_releases",
+            "--formats",
+            "hardcover,hörbuch",
+            "--media-type",
+            "/buecher",
+        ]);
+        assert_eq!(
+            config::create_config(&args).unwrap().search.media_type(),
+            "/buecher"
+        );
+
+        // an empty language searches all languages, "--language" is still accepted
+        let args = Args::parse_from(["upcoming_book_releases", "--language", ""]);
+        assert!(config::create_config(&args)
+            .unwrap()
+            .search
+            .languages
+            .is_empty());
+
+        // other commands only take the global arguments
+        let args = Args::parse_from(["upcoming_book_releases", "show", "-d", "/tmp"]);
+        assert!(args.fetch_args().is_none());
+        assert_eq!(config::create_config(&args).unwrap().dest_release, "/tmp");
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_error_cases() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        // test missing config file
+        assert!(config::read_config("/tmp/this_config_does_not_exist.toml").is_err());
+
+        // test unknown entry and wrong type
+        std::fs::write(CONFIG_FILE, "release_yaer = \"0\"").unwrap();
+        assert!(config::read_config(CONFIG_FILE).is_err());
+        std::fs::write(CONFIG_FILE, "[search]\nmax_results = \"three\"").unwrap();
+        assert!(config::read_config(CONFIG_FILE).is_err());
+
+        // test empty list of book formats
+        std::fs::write(CONFIG_FILE, "[search]\nformats = []").unwrap();
+        let args = Args::parse_from(["upcoming_book_releases", "--config", CONFIG_FILE]);
+        assert!(config::create_config(&args).is_err());
+    }
+}
@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod tests {
+    use crate::customtypes::UpcomingRelease;
+    use crate::details;
+    use crate::fetcher::Fetcher;
+    use crate::logger;
+    use serial_test::serial;
+
+    const LOGLEVEL: &str = "Trace";
+    const FIXTURES_DIR: &str = "src/tests/fixtures";
+    const CACHE_FILE: &str = "/tmp/upcoming-releases-details.json";
+    const PRODUCT_PAGE: &str =
+        "src/tests/fixtures/www.weltbild.de_artikel_buch_ihr-wollt-es-dunkler_41901234-1.html";
+    const URL: &str = "https://www.weltbild.de/artikel/buch/ihr-wollt-es-dunkler_41901234-1";
+    const SERIES_PAGE: &str = r#"
+        <html>
+            <head><meta property="og:description" content="Der siebte Fall für   David Hunter."></head>
+            <body>
+                <h1>Knochenkälte</h1>
+                <span>Reihe: David Hunter</span>
+                <span>Band: 7</span>
+                <span>Übersetzung: Karen Witthuhn und Sabine Längsfeld</span>
+                <span>Erscheinungstermin:</span> <span>02.10.2024</span>
+            </body>
+        </html>"#;
+
+    fn date(year: i32, month: u32, day: u32) -> chrono::DateTime<chrono::Utc> {
+        chrono::NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+    }
+
+    fn release() -> UpcomingRelease {
+        let mut release = UpcomingRelease::create(
+            "Stephen King".to_string(),
+            "Ihr wollt es dunkler".to_string(),
+            date(2024, 5, 21),
+        );
+        release.url = URL.to_string();
+        release.price = "28.00 €".to_string();
+        release
+    }
+
+    #[test]
+    #[serial]
+    fn test_parse_product_page() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        // labels and values in a definition list
+        let details = details::parse_product_page(&std::fs::read_to_string(PRODUCT_PAGE).unwrap());
+        assert_eq!(details.pages, Some(720));
+        assert_eq!(details.translator, "Bernhard Kleinschmidt");
+        assert_eq!(details.original_title, "You Like It Darker");
+        assert_eq!(details.date, "2024-05-21");
+        assert_eq!(
+            details.description,
+            "Zwölf neue Geschichten vom Meister des Schreckens. \
+             Stephen King zeigt, dass die Dunkelheit überall lauert."
+        );
+        assert_eq!(details.series, "");
+        assert_eq!(details.volume, None);
+
+        // labels and values in the same text, the blurb in a meta tag
+        let details = details::parse_product_page(SERIES_PAGE);
+        assert_eq!(details.series, "David Hunter");
+        assert_eq!(details.volume, Some(7));
+        assert_eq!(details.translator, "Karen Witthuhn und Sabine Längsfeld");
+        assert_eq!(details.date, "2024-10-02");
+        assert_eq!(details.description, "Der siebte Fall für David Hunter.");
+        assert_eq!(details.pages, None);
+
+        // nothing found on an empty page
+        assert_eq!(
+            details::parse_product_page("<html></html>"),
+            Default::default()
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_enrich_releases() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let fetcher = Fetcher::Offline(FIXTURES_DIR.into());
+        let without_url = UpcomingRelease::create(
+            "Stephen King".to_string(),
+            "Holly".to_string(),
+            date(2024, 9, 1),
+        );
+
+        let (enriched, cache) =
+            details::enrich_releases(vec![release(), without_url], &fetcher, &[], 2).await;
+        assert_eq!(enriched.len(), 2);
+        assert_eq!(enriched[0].pages, Some(720));
+        assert_eq!(enriched[0].translator, "Bernhard Kleinschmidt");
+        assert_eq!(enriched[0].original_title, "You Like It Darker");
+        assert_eq!(enriched[1].pages, None);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache[0].url, URL);
+
+        // unchanged search results are taken from the cache, the product page is not read again
+        let no_pages = Fetcher::Offline("/tmp/upcoming-releases-no-pages".into());
+        let (enriched, next_cache) =
+            details::enrich_releases(vec![release()], &no_pages, &cache, 2).await;
+        assert_eq!(enriched[0].pages, Some(720));
+        assert_eq!(next_cache, cache);
+
+        // a changed search result is fetched again, if that fails the release is kept as it is
+        let mut changed = release();
+        changed.price = "30.00 €".to_string();
+        let (enriched, next_cache) =
+            details::enrich_releases(vec![changed], &no_pages, &cache, 2).await;
+        assert_eq!(enriched[0].pages, None);
+        assert!(next_cache.is_empty());
+
+        // the exact publication date of the product page replaces the one of the search result
+        let mut cache = cache;
+        cache[0].details = details::parse_product_page(SERIES_PAGE);
+        let (enriched, _) = details::enrich_releases(vec![release()], &no_pages, &cache, 2).await;
+        assert_eq!(enriched[0].date, date(2024, 10, 2));
+        assert_eq!(enriched[0].series, "David Hunter");
+        assert_eq!(enriched[0].volume, Some(7));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_details_cache() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let _ = std::fs::remove_file(CACHE_FILE);
+
+        // no cache file means nothing is cached yet
+        assert!(details::load_cache(CACHE_FILE).unwrap().is_empty());
+
+        let fetcher = Fetcher::Offline(FIXTURES_DIR.into());
+        let (_, cache) = details::enrich_releases(vec![release()], &fetcher, &[], 1).await;
+        details::save_cache(CACHE_FILE, &cache).unwrap();
+        assert_eq!(details::load_cache(CACHE_FILE).unwrap(), cache);
+
+        // a broken cache file is an error
+        std::fs::write(CACHE_FILE, "no json").unwrap();
+        assert!(details::load_cache(CACHE_FILE).is_err());
+
+        // cleanup
+        let _ = std::fs::remove_file(CACHE_FILE);
+        assert!(!std::path::Path::new(CACHE_FILE).exists());
+    }
+}
@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod tests {
+    use crate::customtypes::{Locale, UpcomingRelease};
+    use crate::editions;
+    use crate::logger;
+    use crate::releases;
+    use serial_test::serial;
+
+    const LOGLEVEL: &str = "Trace";
+    const AUTHOR: &str = "Stephen King";
+    const TITLE: &str = "Holly";
+
+    fn release(title: &str, language: &str, day: u32) -> UpcomingRelease {
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 9, day)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc();
+
+        let mut release = UpcomingRelease::create(AUTHOR.to_string(), title.to_string(), date);
+        release.source = "Weltbild".to_string();
+        release.language = language.to_string();
+        release
+    }
+
+    #[test]
+    #[serial]
+    fn test_group_editions() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        // the earliest edition is the entry, the others are listed as its editions
+        let grouped = editions::group_editions(vec![
+            release(TITLE, "ger", 20),
+            release(TITLE, "eng", 5),
+            release("HOLLY", "fre", 25),
+            release("Fairy Tale", "eng", 10),
+        ]);
+
+        assert_eq!(grouped.len(), 2);
+        assert_eq!(grouped[0].language, "eng");
+        assert_eq!(grouped[0].date.format("%d").to_string(), "05");
+        let languages: Vec<&str> = grouped[0]
+            .editions
+            .iter()
+            .map(|edition| edition.language.as_str())
+            .collect();
+        assert_eq!(languages, vec!["ger", "fre"]);
+        assert!(grouped[1].editions.is_empty());
+
+        // other formats and release sources of the same work are grouped, duplicates are dropped
+        let mut paperback = release(TITLE, "ger", 21);
+        paperback.format = "Taschenbuch".to_string();
+        let mut thalia = release(TITLE, "ger", 22);
+        thalia.source = "Thalia".to_string();
+        let grouped = editions::group_editions(vec![
+            release(TITLE, "ger", 20),
+            paperback,
+            thalia.clone(),
+            release(TITLE, "ger", 20),
+            thalia,
+        ]);
+        assert_eq!(grouped.len(), 1);
+        assert_eq!(grouped[0].editions.len(), 2);
+        assert_eq!(grouped[0].editions[0].format, "Taschenbuch");
+        assert_eq!(grouped[0].editions[1].source, "Thalia");
+
+        // releases sharing the ISBN are grouped even if the titles differ
+        let mut first = release(TITLE, "ger", 20);
+        first.isbn = "9783453273634".to_string();
+        let mut second = release("Holly: Roman", "ger", 22);
+        second.isbn = "9783453273634".to_string();
+        let grouped =
+            editions::group_editions(vec![first, second, release("Billy Summers", "", 22)]);
+        assert_eq!(grouped.len(), 2);
+        assert_eq!(grouped[0].editions[0].title, "Holly: Roman");
+        assert!(grouped[1].editions.is_empty());
+
+        // translations are grouped by the title of their original edition
+        let mut german = release("Ihr wollt es dunkler", "ger", 21);
+        german.original_title = "You Like It Darker".to_string();
+        let mut french = release("Vous aimez le noir", "fre", 28);
+        french.original_title = "you like it darker".to_string();
+        let grouped = editions::group_editions(vec![
+            german,
+            release("You Like It Darker", "eng", 20),
+            french,
+            release("Ihr wollt es heller", "ger", 21),
+        ]);
+        assert_eq!(grouped.len(), 2);
+        assert_eq!(grouped[0].title, "You Like It Darker");
+        assert_eq!(grouped[0].original_title, "You Like It Darker");
+        let titles: Vec<&str> = grouped[0]
+            .editions
+            .iter()
+            .map(|edition| edition.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["Ihr wollt es dunkler", "Vous aimez le noir"]);
+
+        // without the original title, a translation is a work of its own
+        let grouped = editions::group_editions(vec![
+            release("Ihr wollt es dunkler", "ger", 21),
+            release("You Like It Darker", "eng", 20),
+        ]);
+        assert_eq!(grouped.len(), 2);
+    }
+
+    #[test]
+    #[serial]
+    fn test_render_editions() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let grouped =
+            editions::group_editions(vec![release(TITLE, "ger", 1), release(TITLE, "eng", 30)]);
+        let contents = releases::render_text(&grouped, Locale::De).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+
+        assert_eq!(lines[4], "Stephen King - \"Holly\" (Weltbild) [ger]");
+        assert_eq!(lines[5], "    eng: \"Holly\" 30. September 2024 (Weltbild)");
+
+        // the language is left out if all editions share it
+        let mut paperback = release(TITLE, "ger", 30);
+        paperback.format = "Taschenbuch".to_string();
+        let grouped = editions::group_editions(vec![release(TITLE, "ger", 1), paperback]);
+        let contents = releases::render_text(&grouped, Locale::De).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+
+        assert_eq!(lines[4], "Stephen King - \"Holly\" (Weltbild)");
+        assert_eq!(
+            lines[5],
+            "    \"Holly\" 30. September 2024 (Taschenbuch, Weltbild)"
+        );
+    }
+}
@@ -0,0 +1,251 @@
+#[cfg(test)]
+mod tests {
+    use crate::customtypes::{Edition, Locale, UpcomingRelease};
+    use crate::export;
+    use crate::logger;
+    use serial_test::serial;
+
+    const LOGLEVEL: &str = "Trace";
+
+    fn release_date(year: i32, month: u32, day: u32) -> chrono::DateTime<chrono::Utc> {
+        chrono::NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+    }
+
+    fn test_releases() -> Vec<UpcomingRelease> {
+        let mut release_1 = UpcomingRelease::create(
+            "Marc Elsberg".to_string(),
+            "°C - Celsius".to_string(),
+            release_date(2024, 9, 9),
+        );
+        release_1.contributors = vec!["Marc Elsberg".to_string()];
+        release_1.source = "Weltbild".to_string();
+        release_1.url = "https://www.weltbild.de/artikel/buch/c-celsius_123-1".to_string();
+        release_1.format = "Hardcover".to_string();
+        release_1.price = "24.00 €".to_string();
+        release_1.publisher = "Blanvalet".to_string();
+        release_1.isbn = "9783764508272".to_string();
+        release_1.cover_url = "https://i.weltbild.de/p/c-celsius_123.jpg".to_string();
+        release_1.tag = "thriller".to_string();
+        release_1.language = "ger".to_string();
+        release_1.editions = vec![Edition {
+            title: "°C - Celsius".to_string(),
+            date: release_date(2024, 10, 1),
+            language: "eng".to_string(),
+            source: "Weltbild".to_string(),
+            url: String::new(),
+            format: "Taschenbuch".to_string(),
+            isbn: String::new(),
+        }];
+
+        let mut release_2 = UpcomingRelease::create(
+            "John Katzenbach".to_string(),
+            "Die Komplizen. Fünf Männer, fünf Mörder, ein \"perfider\" Plan".to_string(),
+            release_date(2024, 7, 1),
+        );
+        release_2.source = "Thalia".to_string();
+        release_2.format = "Taschenbuch".to_string();
+
+        vec![release_1, release_2]
+    }
+
+    #[test]
+    #[serial]
+    fn test_csv_field() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        assert_eq!(export::csv_field("°C - Celsius"), "°C - Celsius");
+        assert_eq!(
+            export::csv_field("Finster, finster"),
+            "\"Finster, finster\""
+        );
+        assert_eq!(
+            export::csv_field("Das \"Böse\" erwacht"),
+            "\"Das \"\"Böse\"\" erwacht\""
+        );
+        assert_eq!(export::csv_field("Zwei\nZeilen"), "\"Zwei\nZeilen\"");
+        assert_eq!(export::csv_field(""), "");
+    }
+
+    #[test]
+    #[serial]
+    fn test_to_csv() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let expected = "author,title,date,source,url,language,editions,format,reissue,isbn,publisher,price,cover_url,series,volume,pages,translator,description\r\n\
+            Marc Elsberg,°C - Celsius,2024-09-09,Weltbild,https://www.weltbild.de/artikel/buch/c-celsius_123-1,ger,eng Taschenbuch 2024-10-01,Hardcover,,9783764508272,Blanvalet,24.00 €,https://i.weltbild.de/p/c-celsius_123.jpg,,,,,\r\n\
+            John Katzenbach,\"Die Komplizen. Fünf Männer, fünf Mörder, ein \"\"perfider\"\" Plan\",2024-07-01,Thalia,,,,Taschenbuch,,,,,,,,,,\r\n";
+
+        assert_eq!(export::to_csv(&test_releases()), expected);
+
+        // only the header is left without releases
+        assert_eq!(
+            export::to_csv(&[]),
+            "author,title,date,source,url,language,editions,format,reissue,isbn,publisher,price,cover_url,series,volume,pages,translator,description\r\n"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_to_json() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let json = export::to_json(&test_releases()).unwrap();
+        let document: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(document["schema_version"], export::SCHEMA_VERSION);
+        assert_eq!(document["releases"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            document["releases"][0],
+            serde_json::json!({
+                "author": "Marc Elsberg",
+                "title": "°C - Celsius",
+                "contributors": ["Marc Elsberg"],
+                "date": "2024-09-09",
+                "source": "Weltbild",
+                "url": "https://www.weltbild.de/artikel/buch/c-celsius_123-1",
+                "cover_url": "https://i.weltbild.de/p/c-celsius_123.jpg",
+                "format": "Hardcover",
+                "price": "24.00 €",
+                "publisher": "Blanvalet",
+                "pages": null,
+                "series": "",
+                "volume": null,
+                "translator": "",
+                "original_title": "",
+                "description": "",
+                "tag": "thriller",
+                "language": "ger",
+                "isbn": "9783764508272",
+                "editions": [{
+                    "title": "°C - Celsius",
+                    "date": "2024-10-01",
+                    "language": "eng",
+                    "source": "Weltbild",
+                    "url": "",
+                    "format": "Taschenbuch",
+                    "isbn": ""
+                }],
+                "reissue": ""
+            })
+        );
+
+        // an empty list of releases is still a valid document
+        let document: serde_json::Value =
+            serde_json::from_str(&export::to_json(&[]).unwrap()).unwrap();
+        assert!(document["releases"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_to_ndjson() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let ndjson = export::to_ndjson(&test_releases()).unwrap();
+        let lines: Vec<&str> = ndjson.lines().collect();
+        assert_eq!(lines.len(), 2);
+
+        // every line is a JSON object of a single release, including the schema version
+        let release: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(release["schema_version"], export::SCHEMA_VERSION);
+        assert_eq!(release["author"], "John Katzenbach");
+        assert_eq!(release["date"], "2024-07-01");
+        assert_eq!(release["format"], "Taschenbuch");
+        assert_eq!(release["url"], "");
+
+        assert!(export::to_ndjson(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_to_ics() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let releases = test_releases();
+        let ics = export::to_ics(&releases, Locale::En);
+
+        // every line ends with CRLF and is not longer than 75 octets
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        let lines: Vec<&str> = ics.split("\r\n").collect();
+        assert!(lines.iter().all(|line| line.len() <= 75));
+
+        // unfold the lines to check the contents
+        let unfolded = ics.replace("\r\n ", "");
+        let lines: Vec<&str> = unfolded.split("\r\n").collect();
+
+        assert_eq!(lines[0], "BEGIN:VCALENDAR");
+        assert_eq!(lines.iter().filter(|&&l| l == "BEGIN:VEVENT").count(), 2);
+        assert!(lines.contains(&"DTSTART;VALUE=DATE:20240909"));
+        assert!(lines.contains(&"DTEND;VALUE=DATE:20240910"));
+        assert!(lines.contains(&"SUMMARY:Marc Elsberg - °C - Celsius"));
+        assert!(lines.contains(
+            &"DESCRIPTION:Format: Hardcover\\nPublisher: Blanvalet\\nPrice: 24.00 €\\nISBN: 9783764508272\\nSource: Weltbild\\nLanguage: ger\\nEditions: eng Taschenbuch 2024-10-01"
+        ));
+
+        // the labels of the details are given in the language of the locale
+        let unfolded = export::to_ics(&releases, Locale::De).replace("\r\n ", "");
+        assert!(unfolded.contains(
+            "DESCRIPTION:Format: Hardcover\\nVerlag: Blanvalet\\nPreis: 24.00 €\\nISBN: 9783764508272\\nQuelle: Weltbild\\nSprache: ger\\nAusgaben: eng Taschenbuch 2024-10-01"
+        ));
+        let unfolded = export::to_ics(&releases, Locale::Fr).replace("\r\n ", "");
+        assert!(unfolded.contains("\\nÉditeur: Blanvalet\\nPrix: 24.00 €\\n"));
+        assert!(lines.contains(
+            &"IMAGE;VALUE=URI;DISPLAY=THUMBNAIL:https://i.weltbild.de/p/c-celsius_123.jpg"
+        ));
+        assert!(lines.contains(
+            &"SUMMARY:John Katzenbach - Die Komplizen. Fünf Männer\\, fünf Mörder\\, ein \"perfider\" Plan"
+        ));
+        assert!(lines.contains(
+            &format!(
+                "UID:{}@upcoming-book-releases",
+                export::ics_uid(&releases[0])
+            )
+            .as_str()
+        ));
+    }
+
+    #[test]
+    #[serial]
+    fn test_ics_uid() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let releases = test_releases();
+
+        // the UID is stable and only depends on author, title and source
+        let mut moved_release = UpcomingRelease::create(
+            releases[0].author.clone(),
+            releases[0].title.clone(),
+            release_date(2025, 1, 1),
+        );
+        moved_release.source = releases[0].source.clone();
+
+        assert_eq!(export::ics_uid(&releases[0]), "ff1aa792cee20dc1");
+        assert_eq!(
+            export::ics_uid(&releases[0]),
+            export::ics_uid(&moved_release)
+        );
+        assert_ne!(export::ics_uid(&releases[0]), export::ics_uid(&releases[1]));
+    }
+
+    #[test]
+    #[serial]
+    fn test_ics_fold() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        // short lines are not folded
+        assert_eq!(export::ics_fold("SUMMARY:Kurz"), "SUMMARY:Kurz");
+
+        // long lines are folded without splitting multi-byte characters
+        let line = "SUMMARY:".to_string() + &"ä".repeat(40);
+        let folded = export::ics_fold(&line);
+        let parts: Vec<&str> = folded.split("\r\n").collect();
+
+        assert_eq!(parts.len(), 2);
+        assert!(parts.iter().all(|part| part.len() <= 75));
+        assert!(parts[1].starts_with(' '));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+}
@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod tests {
+    use crate::fetcher::{self, Fetcher, RetryPolicy};
+    use crate::logger;
+    use crate::ratelimit::RateLimiter;
+    use serial_test::serial;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    const LOGLEVEL: &str = "Trace";
+    const FIXTURES_DIR: &str = "src/tests/fixtures";
+    const NON_EXISTING_DIR: &str = "non-existing-dir";
+    const URL: &str =
+        "https://www.weltbild.de/suche/King+Stephen?jahr=0&node=%2Fbuecher&sprache=%2Flanguage%2Fger";
+    const FILE_NAME: &str =
+        "www.weltbild.de_suche_King_Stephen_jahr_0_node__2Fbuecher_sprache__2Flanguage_2Fger.html";
+
+    const RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(1);
+    const TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+    /// Start a local HTTP server answering the requests with the given status codes, one after
+    /// another. Returns the URL of the server and the number of received requests.
+    async fn start_server(status_codes: Vec<u16>) -> (String, Arc<AtomicUsize>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/suche", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+
+        tokio::spawn(async move {
+            for status_code in status_codes {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buffer = [0; 1024];
+                let _ = stream.read(&mut buffer).await;
+                counter.fetch_add(1, Ordering::SeqCst);
+
+                let response = format!(
+                    "HTTP/1.1 {} Status\r\nContent-Length: 4\r\nConnection: close\r\n\r\nbody",
+                    status_code
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+
+        (url, requests)
+    }
+
+    fn http_fetcher(retries: u32) -> Fetcher {
+        Fetcher::http(
+            TIMEOUT,
+            RateLimiter::create(0.0, 1),
+            RetryPolicy::create(retries, RETRY_DELAY),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    #[serial]
+    fn test_offline_file_name() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        assert_eq!(fetcher::offline_file_name(URL), FILE_NAME);
+        assert_eq!(
+            fetcher::offline_file_name("https://www.thalia.de/suche?sq=Ken+Follett"),
+            "www.thalia.de_suche_sq_Ken_Follett.html"
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_fetch_offline() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let fetcher = Fetcher::Offline(FIXTURES_DIR.into());
+
+        let html_content = fetcher.fetch(URL).await.unwrap();
+        let saved_page =
+            std::fs::read_to_string(std::path::Path::new(FIXTURES_DIR).join(FILE_NAME)).unwrap();
+
+        assert_eq!(html_content, saved_page);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_fetch_with_retries() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        // two transient failures are retried until the request succeeds
+        let (url, requests) = start_server(vec![503, 429, 200]).await;
+        let html_content = http_fetcher(3).fetch(&url).await.unwrap();
+
+        assert_eq!(html_content, "body");
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    #[serial]
+    fn test_retry_delay() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let base_delay = std::time::Duration::from_millis(100);
+        let retry_policy = RetryPolicy::create(10, base_delay);
+
+        // the delay doubles with every retry, randomized between half and the full delay
+        for retry in 0..4 {
+            let delay = retry_policy.delay(retry);
+            let exponential = base_delay * 2_u32.pow(retry);
+
+            assert!(delay >= exponential / 2 && delay <= exponential);
+        }
+
+        // the delay is capped at the maximum delay
+        assert!(retry_policy.delay(20) <= retry_policy.max_delay);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_fetcher_error_cases() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        // test URL without saved HTML page
+        let fetcher = Fetcher::Offline(FIXTURES_DIR.into());
+        assert!(fetcher
+            .fetch("https://www.weltbild.de/suche/")
+            .await
+            .is_err());
+
+        // test non-existing directory
+        let fetcher = Fetcher::Offline(NON_EXISTING_DIR.into());
+        assert!(fetcher.fetch(URL).await.is_err());
+
+        // test giving up after the maximum number of retries
+        let (url, requests) = start_server(vec![503, 503, 503]).await;
+        assert!(http_fetcher(2).fetch(&url).await.is_err());
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+
+        // test no retry for client errors
+        let (url, requests) = start_server(vec![404, 200]).await;
+        assert!(http_fetcher(3).fetch(&url).await.is_err());
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,5 +1,6 @@
 #[cfg(test)]
 mod tests {
+    use crate::customtypes::MediaFormat;
     use crate::format;
     use crate::logger;
     use serial_test::serial;
@@ -86,6 +87,128 @@ mod tests {
         );
     }
 
+    #[test]
+    #[serial]
+    fn test_normalize_title() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        assert_eq!(
+            format::normalize_title("  Knochenkälte /  David Hunter\tBd.7 "),
+            "knochenkälte / david hunter bd.7"
+        );
+        assert_eq!(format::normalize_title(""), "");
+    }
+
+    #[test]
+    #[serial]
+    fn test_normalize_author_name() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        assert_eq!(
+            format::normalize_author_name("Lagerlöf, Selma"),
+            "lagerlof, selma"
+        );
+        assert_eq!(
+            format::normalize_author_name(" LAGERLOF,  Selma "),
+            "lagerlof, selma"
+        );
+        assert_eq!(
+            format::normalize_author_name("Adler-Olsen, Jussi"),
+            "adler-olsen, jussi"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_split_contributors() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        assert_eq!(
+            format::split_contributors(FORMATTED_AUTHOR_1),
+            vec![FORMATTED_AUTHOR_1]
+        );
+        assert_eq!(
+            format::split_contributors("Douglas Preston (Autor), Lincoln Child (Autor)"),
+            vec!["Douglas Preston", "Lincoln Child"]
+        );
+        assert_eq!(
+            format::split_contributors("Stephen King und Owen King; Peter Straub & Joe Hill"),
+            vec!["Stephen King", "Owen King", "Peter Straub", "Joe Hill"]
+        );
+        assert!(format::split_contributors(" ").is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_is_contributor() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let contributors = vec!["Douglas Preston".to_string(), "Lincoln Child".to_string()];
+
+        assert!(format::is_contributor(&contributors, "Lincoln Child"));
+        assert!(format::is_contributor(&contributors, "douglas  preston"));
+        assert!(!format::is_contributor(&contributors, "Lincoln"));
+        assert!(!format::is_contributor(&contributors, ""));
+        assert!(!format::is_contributor(&[], FORMATTED_AUTHOR_3));
+    }
+
+    #[test]
+    #[serial]
+    fn test_detect_media_format() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let detected = [
+            ("Buch (Gebunden)", Some(MediaFormat::Hardcover)),
+            ("Taschenbuch", Some(MediaFormat::Taschenbuch)),
+            ("Buch (Kartoniert)", Some(MediaFormat::Taschenbuch)),
+            ("Buch (Broschiert)", Some(MediaFormat::Taschenbuch)),
+            ("Paperback", Some(MediaFormat::Taschenbuch)),
+            ("Hardcover", Some(MediaFormat::Hardcover)),
+            ("eBook (ePUB)", Some(MediaFormat::EBook)),
+            ("Hörbuch (Audio-CD)", Some(MediaFormat::Hoerbuch)),
+            (
+                "Hörbuch-Download (MP3)",
+                Some(MediaFormat::HoerbuchDownload),
+            ),
+            ("Kalender", None),
+        ];
+
+        for (description, media_format) in detected {
+            assert_eq!(format::detect_media_format(description), media_format);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_language_code() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        assert_eq!(format::language_code("/language/eng"), "eng");
+        assert_eq!(format::language_code("/language/ger/"), "ger");
+        assert_eq!(format::language_code("fre"), "fre");
+        assert_eq!(format::language_code(""), "");
+    }
+
+    #[test]
+    #[serial]
+    fn test_find_isbn() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        assert_eq!(
+            format::find_isbn("Taschenbuch\nISBN: 978-3-453-27363-4\n13.00 €").as_deref(),
+            Some("9783453273634")
+        );
+        assert_eq!(
+            format::find_isbn("EAN 9783453273634").as_deref(),
+            Some("9783453273634")
+        );
+
+        // the check digit has to match and other numbers are no ISBN
+        assert_eq!(format::find_isbn("ISBN: 978-3-453-27363-5"), None);
+        assert_eq!(format::find_isbn("Erscheint am 30.09.2024"), None);
+        assert_eq!(format::find_isbn("Artikelnummer 1234567890123"), None);
+    }
+
     #[test]
     #[serial]
     fn test_format_error_cases() {
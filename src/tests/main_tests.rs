@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    use crate::cli::Args;
+    use crate::config;
+    use crate::logger;
+    use clap::Parser;
+    use serial_test::serial;
+
+    const LOGLEVEL: &str = "Trace";
+    const AUTHORS_FILE: &str = "src/tests/test_authors";
+    const NO_PAGES_DIR: &str = "/tmp/upcoming-releases-no-pages";
+    const DEST: &str = "/tmp/upcoming-releases-failed";
+    const FIXTURES_DIR: &str = "src/tests/fixtures";
+    const CHANGES_DEST: &str = "/tmp/upcoming-releases-only-changes";
+
+    #[tokio::test]
+    #[serial]
+    async fn test_fetch_all_authors_failed() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let _ = std::fs::remove_dir_all(DEST);
+
+        // no saved HTML page means no author can be checked
+        let args = Args::parse_from([
+            "upcoming_book_releases",
+            "-a",
+            AUTHORS_FILE,
+            "-d",
+            DEST,
+            "-o",
+            NO_PAGES_DIR,
+        ]);
+        let config = config::create_config(&args).unwrap();
+
+        let err = crate::fetch(&config, args.fetch_args().unwrap())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("could be checked"));
+
+        // the release file is left unchanged
+        assert!(!std::path::Path::new(DEST).exists());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_fetch_only_changes_without_changes() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let _ = std::fs::remove_dir_all(CHANGES_DEST);
+        std::fs::create_dir_all(CHANGES_DEST).unwrap();
+        let release_file = format!("{}/releases", CHANGES_DEST);
+        let state_file = format!("{}/state.json", CHANGES_DEST);
+
+        // the saved pages only hold releases of the past, so nothing changed since the last run
+        std::fs::write(
+            &release_file,
+            "[new] Ken Follett - \"Never\" (2024-07-26)\n",
+        )
+        .unwrap();
+        let args = Args::parse_from([
+            "upcoming_book_releases",
+            "-a",
+            AUTHORS_FILE,
+            "-d",
+            CHANGES_DEST,
+            "-o",
+            FIXTURES_DIR,
+            "--sources",
+            "weltbild",
+            "--state-file",
+            &state_file,
+            "--only-changes",
+        ]);
+        let config = config::create_config(&args).unwrap();
+        crate::fetch(&config, args.fetch_args().unwrap())
+            .await
+            .unwrap();
+
+        // the changes of the last run are not left in the release file
+        assert_eq!(
+            std::fs::read_to_string(&release_file).unwrap(),
+            "Upcoming Book Releases\n"
+        );
+
+        // cleanup
+        let _ = std::fs::remove_dir_all(CHANGES_DEST);
+        assert!(!std::path::Path::new(CHANGES_DEST).exists());
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use crate::logger;
+    use crate::ratelimit::RateLimiter;
+    use serial_test::serial;
+
+    const LOGLEVEL: &str = "Trace";
+    const URL_1: &str = "https://www.weltbild.de/suche/King+Stephen";
+    const URL_2: &str = "https://www.weltbild.de/suche/Follett+Ken";
+    const URL_3: &str = "https://www.thalia.de/suche?sq=Stephen+King";
+    const REQUESTS_PER_SECOND: f64 = 20.0;
+
+    #[tokio::test]
+    #[serial]
+    async fn test_rate_limit_per_host() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let rate_limiter = RateLimiter::create(REQUESTS_PER_SECOND, 1);
+        let start = tokio::time::Instant::now();
+
+        // the first request is sent immediately, the following ones have to wait 50ms each
+        rate_limiter.acquire(URL_1).await;
+        rate_limiter.acquire(URL_2).await;
+        rate_limiter.acquire(URL_1).await;
+        assert!(start.elapsed() >= std::time::Duration::from_millis(95));
+
+        // another host has its own token bucket
+        let start = tokio::time::Instant::now();
+        rate_limiter.acquire(URL_3).await;
+        assert!(start.elapsed() < std::time::Duration::from_millis(45));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_rate_limit_burst() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        // a burst of three requests is sent without waiting
+        let rate_limiter = RateLimiter::create(1.0, 3);
+        let start = tokio::time::Instant::now();
+
+        for _ in 0..3 {
+            rate_limiter.acquire(URL_1).await;
+        }
+        assert!(start.elapsed() < std::time::Duration::from_millis(500));
+
+        // a rate of zero, a negative rate or no number disables the rate limiter
+        for requests_per_second in [0.0, -1.0, f64::NAN] {
+            let rate_limiter = RateLimiter::create(requests_per_second, 1);
+            let start = tokio::time::Instant::now();
+
+            for _ in 0..10 {
+                rate_limiter.acquire(URL_1).await;
+            }
+            assert!(start.elapsed() < std::time::Duration::from_millis(500));
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_rate_limit_tiny_rate() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        // a wait too long to be represented does not panic, the request is just not sent
+        let rate_limiter = RateLimiter::create(f64::MIN_POSITIVE, 1);
+        rate_limiter.acquire(URL_1).await;
+
+        let waiting = tokio::time::timeout(
+            std::time::Duration::from_millis(50),
+            rate_limiter.acquire(URL_1),
+        )
+        .await;
+        assert!(waiting.is_err());
+    }
+}
@@ -0,0 +1,249 @@
+#[cfg(test)]
+mod tests {
+    use crate::customtypes::{KnownTitle, Locale, UpcomingRelease};
+    use crate::logger;
+    use crate::reissues;
+    use serial_test::serial;
+
+    const LOGLEVEL: &str = "Trace";
+    const HISTORY_FILE: &str = "/tmp/upcoming-releases-history.json";
+    const AUTHOR: &str = "Ken Follett";
+    const TITLE: &str = "Der dritte Zwilling";
+
+    fn date(year: i32, month: u32, day: u32) -> chrono::DateTime<chrono::Utc> {
+        chrono::NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+    }
+
+    #[test]
+    #[serial]
+    fn test_detect_reissue() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        // markers are whole words, case is ignored
+        assert_eq!(
+            reissues::detect_reissue("Die Säulen der Erde - Jubiläumsausgabe", 2024).as_deref(),
+            Some("Jubiläumsausgabe")
+        );
+        assert_eq!(
+            reissues::detect_reissue("Die Jahrhundert-Trilogie (3 Bände im SCHUBER)", 2024)
+                .as_deref(),
+            Some("Schuber")
+        );
+        assert_eq!(reissues::detect_reissue("Der Boxer", 2024), None);
+        assert_eq!(
+            reissues::detect_reissue("Harry Potter Box-Set: Band 1-7", 2024).as_deref(),
+            Some("Box-Set")
+        );
+        assert_eq!(
+            reissues::detect_reissue("Jack Reacher Box Set", 2024).as_deref(),
+            Some("Box-Set")
+        );
+
+        // a "Box" alone is no box set, e.g. in a title
+        assert_eq!(
+            reissues::detect_reissue("Die Box - Thriller Taschenbuch", 2024),
+            None
+        );
+        assert_eq!(reissues::detect_reissue("Box 21 Taschenbuch", 2024), None);
+
+        // the year of first publication has to be before the release
+        assert_eq!(
+            reissues::detect_reissue("Der dritte Zwilling Erstveröffentlichung: 1996", 2024)
+                .as_deref(),
+            Some("first published 1996")
+        );
+        assert_eq!(
+            reissues::detect_reissue("Never erstmals erschienen im Jahr 2024", 2024),
+            None
+        );
+
+        // a translation is no reissue of its original
+        for text in [
+            "Holly Originalausgabe: 2023, deutsche Erstausgabe 2024",
+            "Holly Original-Erstveröffentlichung 2023",
+            "Holly im Original erstmals erschienen 2023",
+        ] {
+            assert_eq!(reissues::detect_reissue(text, 2024), None);
+        }
+        assert_eq!(
+            reissues::detect_reissue("Never Taschenbuch 16.00 €", 2024),
+            None
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_describe_reason() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        // the reasons are stored in English and described in the language of the locale
+        for (locale, first_published, published) in [
+            (
+                Locale::De,
+                "erstmals erschienen 1996",
+                "erschienen am 1. März 1996",
+            ),
+            (
+                Locale::En,
+                "first published 1996",
+                "published March 1, 1996",
+            ),
+            (
+                Locale::Fr,
+                "première parution 1996",
+                "paru le 1er mars 1996",
+            ),
+        ] {
+            assert_eq!(
+                reissues::describe_reason("first published 1996", locale),
+                first_published
+            );
+            assert_eq!(
+                reissues::describe_reason("published 1996-03-01", locale),
+                published
+            );
+
+            // a marker is taken from the search result as it is
+            assert_eq!(
+                reissues::describe_reason("Sonderausgabe", locale),
+                "Sonderausgabe"
+            );
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_mark_known_titles() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let today = chrono::NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+        let history = vec![
+            KnownTitle {
+                author: AUTHOR.to_string(),
+                title: TITLE.to_string(),
+                date: date(1996, 3, 1),
+                isbn: String::new(),
+                url: String::new(),
+            },
+            KnownTitle {
+                author: AUTHOR.to_string(),
+                title: "Never".to_string(),
+                date: date(2024, 7, 1),
+                isbn: String::new(),
+                url: String::new(),
+            },
+        ];
+
+        let mut releases = vec![
+            UpcomingRelease::create(
+                AUTHOR.to_string(),
+                "der  dritte Zwilling".to_string(),
+                date(2024, 7, 26),
+            ),
+            // a postponed release is no reissue, it is not published yet
+            UpcomingRelease::create(AUTHOR.to_string(), "Never".to_string(), date(2024, 7, 26)),
+            UpcomingRelease::create(
+                "Stephen King".to_string(),
+                TITLE.to_string(),
+                date(2024, 7, 26),
+            ),
+        ];
+        reissues::mark_known_titles(&mut releases, &history, today);
+
+        assert_eq!(releases[0].reissue, "published 1996-03-01");
+        assert_eq!(releases[1].reissue, "");
+        assert_eq!(releases[2].reissue, "");
+
+        // every title is remembered once with its earliest release date
+        let updated = reissues::update_history(&history, &releases);
+        assert_eq!(updated.len(), 3);
+        assert_eq!(updated[0].date, date(1996, 3, 1));
+        assert_eq!(updated[1].date, date(2024, 7, 1));
+        assert_eq!(updated[2].author, "Stephen King");
+    }
+
+    #[test]
+    #[serial]
+    fn test_postponed_release() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        const URL: &str = "https://www.weltbild.de/artikel/buch/never_12345";
+
+        // first run: the release is upcoming on 2024-07-01
+        let mut release =
+            UpcomingRelease::create(AUTHOR.to_string(), "Never".to_string(), date(2024, 7, 1));
+        release.url = URL.to_string();
+        let mut releases = vec![release.clone()];
+        reissues::mark_known_titles(
+            &mut releases,
+            &[],
+            chrono::NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+        );
+        let history = reissues::update_history(&[], &releases);
+        assert_eq!(history[0].url, URL);
+
+        // next run after the original date: the same product page is postponed, no reissue
+        release.date = date(2024, 10, 1);
+        let mut releases = vec![release.clone()];
+        reissues::mark_known_titles(
+            &mut releases,
+            &history,
+            chrono::NaiveDate::from_ymd_opt(2024, 8, 1).unwrap(),
+        );
+        assert_eq!(releases[0].reissue, "");
+
+        // the postponed date replaces the original one
+        let history = reissues::update_history(&history, &releases);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].date, date(2024, 10, 1));
+
+        // another product of the same title after publication is a reissue
+        let mut reissue =
+            UpcomingRelease::create(AUTHOR.to_string(), "Never".to_string(), date(2025, 9, 1));
+        reissue.url = "https://www.weltbild.de/artikel/buch/never_67890".to_string();
+        let mut releases = vec![reissue];
+        reissues::mark_known_titles(
+            &mut releases,
+            &history,
+            chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+        );
+        assert_eq!(releases[0].reissue, "published 2024-10-01");
+
+        // the date of the first publication is kept
+        let history = reissues::update_history(&history, &releases);
+        assert_eq!(history[0].date, date(2024, 10, 1));
+        assert_eq!(history[0].url, URL);
+    }
+
+    #[test]
+    #[serial]
+    fn test_history_file() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let _ = std::fs::remove_file(HISTORY_FILE);
+
+        // no history file means no title is known yet
+        assert!(reissues::load_history(HISTORY_FILE).unwrap().is_empty());
+
+        let history = vec![KnownTitle {
+            author: AUTHOR.to_string(),
+            title: TITLE.to_string(),
+            date: date(1996, 3, 1),
+            isbn: "9783404148349".to_string(),
+            url: String::new(),
+        }];
+        reissues::save_history(HISTORY_FILE, &history).unwrap();
+        assert_eq!(reissues::load_history(HISTORY_FILE).unwrap(), history);
+
+        // a broken history file is an error
+        std::fs::write(HISTORY_FILE, "[{\"author\": 1}]").unwrap();
+        assert!(reissues::load_history(HISTORY_FILE).is_err());
+
+        // cleanup
+        let _ = std::fs::remove_file(HISTORY_FILE);
+    }
+}
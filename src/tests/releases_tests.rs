@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use crate::customtypes::UpcomingRelease;
+    use crate::customtypes::{Locale, OutputFormat, UpcomingRelease};
     use crate::logger;
     use crate::releases;
     use serial_test::serial;
@@ -48,11 +48,9 @@ mod tests {
             ),
         ];
 
-        let success: bool;
-        match releases::create_releases(releases, DEST, FILE_NAME) {
-            Ok(_) => success = true,
-            Err(_) => success = false,
-        };
+        let success =
+            releases::create_releases(releases, DEST, FILE_NAME, OutputFormat::Text, Locale::De)
+                .is_ok();
         assert!(success);
         assert!(std::path::Path::new(RELEASE_FILE).exists());
 
@@ -96,12 +94,203 @@ mod tests {
 
     #[test]
     #[serial]
-    fn test_releases_error_cases() {
+    fn test_release_file_creation_csv() {
         logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
 
-        // pass empty vector
-        let empty_vector = Vec::new();
-        assert!(releases::create_releases(empty_vector, DEST, FILE_NAME).is_err());
+        let _ = std::fs::remove_file(RELEASE_FILE);
+        assert!(!std::path::Path::new(RELEASE_FILE).exists());
+
+        let today = chrono::Utc::now();
+        let releases: Vec<UpcomingRelease> = vec![
+            UpcomingRelease::create(
+                AUTHOR_1.to_string(),
+                TITLE_1.to_string(),
+                today.checked_add_signed(chrono::Duration::days(3)).unwrap(),
+            ),
+            UpcomingRelease::create(AUTHOR_2.to_string(), TITLE_2.to_string(), today),
+        ];
+
+        assert!(releases::create_releases(
+            releases,
+            DEST,
+            FILE_NAME,
+            OutputFormat::Csv,
+            Locale::De
+        )
+        .is_ok());
+
+        // the releases are sorted by date and the author with a comma is quoted
+        let contents = std::fs::read_to_string(RELEASE_FILE).unwrap();
+        let lines: Vec<&str> = contents.split("\r\n").collect();
+
+        assert_eq!(
+            lines[0],
+            "author,title,date,source,url,language,editions,format,reissue,isbn,publisher,price,cover_url,series,volume,pages,translator,description"
+        );
+        assert!(lines[1].starts_with(&format!("\"{}\",{},", AUTHOR_2, TITLE_2)));
+        assert!(lines[2].starts_with(&format!("\"{}\",{},", AUTHOR_1, TITLE_1)));
+
+        // the release file of the last run is shown as it is
+        assert_eq!(releases::read_releases(DEST, FILE_NAME).unwrap(), contents);
+
+        // without upcoming releases, only the header is left
+        assert!(releases::create_releases(
+            Vec::new(),
+            DEST,
+            FILE_NAME,
+            OutputFormat::Csv,
+            Locale::De
+        )
+        .is_ok());
+        assert_eq!(
+            std::fs::read_to_string(RELEASE_FILE).unwrap(),
+            format!("{}\r\n", lines[0])
+        );
+
+        // cleanup
+        let _ = std::fs::remove_file(RELEASE_FILE);
+        assert!(!std::path::Path::new(RELEASE_FILE).exists());
+    }
+
+    #[test]
+    #[serial]
+    fn test_render_text_locales() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let date = chrono::DateTime::parse_from_rfc3339("2024-09-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let mut release = UpcomingRelease::create(AUTHOR_2.to_string(), TITLE_2.to_string(), date);
+        release.source = "Weltbild".to_string();
+        let releases = vec![release];
+        let separator =
+            "-----------------------------------------------------------------------------------";
+
+        // German is the default and stays unchanged
+        assert_eq!(
+            releases::render_text(&releases, Locale::De).unwrap(),
+            format!(
+                "Upcoming Book Releases\n\n1. September 2024\n{}\n{} - \"{}\" (Weltbild)\n",
+                separator, AUTHOR_2, TITLE_2
+            )
+        );
+        assert_eq!(
+            releases::render_text(&releases, Locale::En).unwrap(),
+            format!(
+                "Upcoming Book Releases\n\nSeptember 1, 2024\n{}\n{} - \"{}\" (Weltbild)\n",
+                separator, AUTHOR_2, TITLE_2
+            )
+        );
+        assert_eq!(
+            releases::render_text(&releases, Locale::Fr).unwrap(),
+            format!(
+                "Prochaines parutions\n\n1er septembre 2024\n{}\n{} - « {} » (Weltbild)\n",
+                separator, AUTHOR_2, TITLE_2
+            )
+        );
+
+        // the format is shown together with the release source
+        let mut releases = releases;
+        releases[0].format = "Taschenbuch".to_string();
+        assert!(releases::render_text(&releases, Locale::De)
+            .unwrap()
+            .ends_with(" - \"Shining\" (Taschenbuch, Weltbild)\n"));
+
+        // the product details are shown as well, the product page below the release
+        releases[0].publisher = "Heyne".to_string();
+        releases[0].price = "12.00 €".to_string();
+        releases[0].isbn = "9783453438262".to_string();
+        releases[0].url = "https://www.weltbild.de/artikel/buch/shining_123-1".to_string();
+        assert!(releases::render_text(&releases, Locale::De)
+            .unwrap()
+            .ends_with(
+                " - \"Shining\" (Taschenbuch, Heyne, 12.00 €, Weltbild, ISBN 9783453438262)\n    \
+                 <https://www.weltbild.de/artikel/buch/shining_123-1>\n"
+            ));
+
+        // the cover image is labeled below the product page
+        releases[0].cover_url = "https://www.weltbild.de/media/shining.jpg".to_string();
+        for (locale, label) in [
+            (Locale::De, "Cover"),
+            (Locale::En, "Cover"),
+            (Locale::Fr, "Couverture"),
+        ] {
+            assert!(releases::render_text(&releases, locale)
+                .unwrap()
+                .ends_with(&format!(
+                    "    <https://www.weltbild.de/artikel/buch/shining_123-1>\n    \
+                     {}: <https://www.weltbild.de/media/shining.jpg>\n",
+                    label
+                )));
+        }
+
+        // the reason of a reissue is labeled in the language of the locale
+        releases[0].reissue = "Sonderausgabe".to_string();
+        for (locale, label) in [
+            (Locale::De, "Neuauflage"),
+            (Locale::En, "reissue"),
+            (Locale::Fr, "réédition"),
+        ] {
+            assert!(releases::render_text(&releases, locale)
+                .unwrap()
+                .contains(&format!("ISBN 9783453438262, {}: Sonderausgabe)", label)));
+        }
+
+        // so is the reason of a title published before
+        releases[0].reissue = "published 1996-03-01".to_string();
+        for (locale, reason) in [
+            (Locale::De, "Neuauflage: erschienen am 1. März 1996)"),
+            (Locale::En, "reissue: published March 1, 1996)"),
+            (Locale::Fr, "réédition: paru le 1er mars 1996)"),
+        ] {
+            assert!(releases::render_text(&releases, locale)
+                .unwrap()
+                .contains(reason));
+        }
+
+        // only the first day of a month is an ordinal in French
+        let date = date.checked_add_signed(chrono::Duration::days(29)).unwrap();
+        assert_eq!(
+            releases::format_date(&date, Locale::De),
+            "30. September 2024"
+        );
+        assert_eq!(
+            releases::format_date(&date, Locale::En),
+            "September 30, 2024"
+        );
+        assert_eq!(
+            releases::format_date(&date, Locale::Fr),
+            "30 septembre 2024"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_releases_to_stdout() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let releases: Vec<UpcomingRelease> = vec![UpcomingRelease::create(
+            AUTHOR_2.to_string(),
+            TITLE_2.to_string(),
+            chrono::Utc::now(),
+        )];
+
+        // no destination dir and no release file is created
+        assert!(releases::create_releases(
+            releases,
+            releases::STDOUT,
+            FILE_NAME,
+            OutputFormat::Ndjson,
+            Locale::De
+        )
+        .is_ok());
+        assert!(!std::path::Path::new(releases::STDOUT).exists());
+    }
+
+    #[test]
+    #[serial]
+    fn test_releases_error_cases() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
 
         // pass incomplete data
         let missing_author: Vec<UpcomingRelease> = vec![UpcomingRelease::create(
@@ -110,7 +299,14 @@ mod tests {
             chrono::Utc::now(),
         )];
 
-        assert!(releases::create_releases(missing_author, DEST, FILE_NAME).is_err());
+        assert!(releases::create_releases(
+            missing_author,
+            DEST,
+            FILE_NAME,
+            OutputFormat::Text,
+            Locale::De
+        )
+        .is_err());
 
         let missing_title: Vec<UpcomingRelease> = vec![UpcomingRelease::create(
             AUTHOR_2.to_string(),
@@ -118,7 +314,21 @@ mod tests {
             chrono::Utc::now(),
         )];
 
-        assert!(releases::create_releases(missing_title, DEST, FILE_NAME).is_err());
+        assert!(releases::create_releases(
+            missing_title,
+            DEST,
+            FILE_NAME,
+            OutputFormat::Text,
+            Locale::De
+        )
+        .is_err());
+
+        // no release file of a last run
+        assert!(releases::read_releases(DEST, "non-existing-releases").is_err());
+
+        // a release file printed to stdout cannot be read again
+        let err = releases::read_releases("-", FILE_NAME).unwrap_err();
+        assert!(err.to_string().contains("stdout"));
 
         // cleanup
         let _ = std::fs::remove_file(RELEASE_FILE);
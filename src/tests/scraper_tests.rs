@@ -1,12 +1,42 @@
 #[cfg(test)]
 mod tests {
     use crate::authors;
+    use crate::config::SearchConfig;
+    use crate::customtypes::Author;
+    use crate::fetcher::Fetcher;
     use crate::logger;
     use crate::scraper;
+    use crate::sources;
     use serial_test::serial;
 
     const AUTHORS_FILE: &str = "src/tests/test_authors";
+    const FIXTURES_DIR: &str = "src/tests/fixtures";
     const LOGLEVEL: &str = "Trace";
+    const CONCURRENCY: usize = 4;
+
+    /// The day the saved Weltbild pages are compared with.
+    fn today() -> chrono::NaiveDate {
+        chrono::NaiveDate::from_ymd_opt(2024, 5, 1).unwrap()
+    }
+
+    /// The releases (author, title, release date) expected from the saved Weltbild pages.
+    const EXPECTED_RELEASES: [(&str, &str, &str); 7] = [
+        ("Chris Carter", "Der Totenarzt", "2024-05-30"),
+        (
+            "Andreas Eschbach",
+            "Der schlauste Mann der Welt",
+            "2024-08-30",
+        ),
+        (
+            "Ken Follett",
+            "Never - Die letzte Entscheidung",
+            "2024-07-26",
+        ),
+        ("Ken Follett", "Der dritte Zwilling", "2024-07-26"),
+        ("Ken Follett", "Die Kinder von Eden", "2024-07-26"),
+        ("Ken Follett", "Die Säulen der Erde", "2024-07-26"),
+        ("Stephen King", "Ihr wollt es dunkler", "2024-05-21"),
+    ];
 
     #[tokio::test]
     #[serial]
@@ -21,13 +51,89 @@ mod tests {
             + "/"
             + AUTHORS_FILE;
         let authors = authors::read_authors(&authors_file);
+        let release_sources =
+            sources::create_sources(&["weltbild".to_string()], &SearchConfig::default(), today())
+                .unwrap();
+        let fetcher = Fetcher::Offline(FIXTURES_DIR.into());
+
+        let scrape_result =
+            scraper::parse_contents(authors.unwrap(), &release_sources, &fetcher, CONCURRENCY)
+                .await
+                .expect("Failed to parse saved HTML pages");
+        assert!(scrape_result.failed_authors.is_empty());
+
+        let releases = scrape_result.releases;
 
-        let success: bool;
-        match scraper::parse_contents(authors.unwrap()).await {
-            Ok(_) => success = true,
-            Err(_) => success = false,
+        let extracted: Vec<(&str, &str, String)> = releases
+            .iter()
+            .map(|release| {
+                assert_eq!(release.source, "Weltbild");
+                (
+                    release.author.as_str(),
+                    release.title.as_str(),
+                    release.date.format("%Y-%m-%d").to_string(),
+                )
+            })
+            .collect();
+        let expected: Vec<(&str, &str, String)> = EXPECTED_RELEASES
+            .iter()
+            .map(|&(author, title, date)| (author, title, date.to_string()))
+            .collect();
+
+        assert_eq!(extracted, expected);
+
+        // the product details are captured from the result tile
+        let release = releases.last().unwrap();
+        assert_eq!(release.isbn, "9783453274402");
+        assert_eq!(release.publisher, "Heyne");
+        assert_eq!(release.format, "Hardcover");
+        assert_eq!(release.price, "28.00 €");
+        assert_eq!(
+            release.url,
+            "https://www.weltbild.de/artikel/buch/ihr-wollt-es-dunkler_41901234-1"
+        );
+        assert_eq!(
+            release.cover_url,
+            "https://i.weltbild.de/p/ihr-wollt-es-dunkler_41901234.jpg"
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_parse_contents_max_results() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        // only the first releases of an author are kept if there are more than the maximum
+        let search = SearchConfig {
+            max_results: 3,
+            ..SearchConfig::default()
         };
-        assert!(success);
+        let release_sources =
+            sources::create_sources(&["weltbild".to_string()], &search, today()).unwrap();
+        let fetcher = Fetcher::Offline(FIXTURES_DIR.into());
+
+        let scrape_result = scraper::parse_contents(
+            vec![Author::create("Follett, Ken".to_string())],
+            &release_sources,
+            &fetcher,
+            CONCURRENCY,
+        )
+        .await
+        .unwrap();
+
+        let titles: Vec<&str> = scrape_result
+            .releases
+            .iter()
+            .map(|release| release.title.as_str())
+            .collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Never - Die letzte Entscheidung",
+                "Der dritte Zwilling",
+                "Die Kinder von Eden"
+            ]
+        );
     }
 
     #[tokio::test]
@@ -35,8 +141,42 @@ mod tests {
     async fn test_scraper_error_cases() {
         logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
 
+        let release_sources =
+            sources::create_sources(&["weltbild".to_string()], &SearchConfig::default(), today())
+                .unwrap();
+        let fetcher = Fetcher::Offline(FIXTURES_DIR.into());
+
         // test empty list of authors
-        let empty_authors: Vec<String> = Vec::new();
-        assert!(scraper::parse_contents(empty_authors).await.is_err());
+        let empty_authors: Vec<Author> = Vec::new();
+        assert!(
+            scraper::parse_contents(empty_authors, &release_sources, &fetcher, CONCURRENCY)
+                .await
+                .is_err()
+        );
+
+        // test empty chain of release sources
+        assert!(scraper::parse_contents(
+            vec![Author::create("King, Stephen".to_string())],
+            &[],
+            &fetcher,
+            CONCURRENCY
+        )
+        .await
+        .is_err());
+
+        // test missing saved HTML page, so every release source fails. The other authors are
+        // still processed
+        let authors = vec![
+            Author::create("King, Stephen".to_string()),
+            Author::create("Brown, Dan".to_string()),
+        ];
+        let scrape_result =
+            scraper::parse_contents(authors, &release_sources, &fetcher, CONCURRENCY)
+                .await
+                .unwrap();
+
+        assert_eq!(scrape_result.releases.len(), 1);
+        assert_eq!(scrape_result.failed_authors.len(), 1);
+        assert_eq!(scrape_result.failed_authors[0].0.name, "Brown, Dan");
     }
 }
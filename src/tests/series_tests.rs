@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod tests {
+    use crate::customtypes::{Locale, UpcomingRelease};
+    use crate::logger;
+    use crate::releases;
+    use crate::series::{self, SeriesTitle};
+    use serial_test::serial;
+
+    const LOGLEVEL: &str = "Trace";
+    const AUTHOR: &str = "Jussi Adler-Olsen";
+
+    fn series_title(title: &str, series: &str, volume: Option<u32>) -> SeriesTitle {
+        SeriesTitle {
+            title: title.to_string(),
+            series: series.to_string(),
+            volume,
+        }
+    }
+
+    fn release(title: &str, volume: Option<u32>, day: u32) -> UpcomingRelease {
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 9, day)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc();
+
+        let mut release = UpcomingRelease::create(AUTHOR.to_string(), title.to_string(), date);
+        release.series = "Sonderdezernat Q".to_string();
+        release.volume = volume;
+        release
+    }
+
+    #[test]
+    #[serial]
+    fn test_parse_series() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        // the hero in front of the series name is dropped
+        assert_eq!(
+            series::parse_series("Verraten / Carl Mørck. Sonderdezernat Q Bd.10"),
+            series_title("Verraten", "Sonderdezernat Q", Some(10))
+        );
+        assert_eq!(
+            series::parse_series("Die Familie / Dr. Frederick Starks Bd.3"),
+            series_title("Die Familie", "Dr. Frederick Starks", Some(3))
+        );
+        assert_eq!(
+            series::parse_series("Knochenkälte / David Hunter Bd. 7"),
+            series_title("Knochenkälte", "David Hunter", Some(7))
+        );
+        assert_eq!(
+            series::parse_series("Der Grauzorn / Minen der Macht Band 3"),
+            series_title("Der Grauzorn", "Minen der Macht", Some(3))
+        );
+
+        // titles without explicit series or without volume are kept as they are
+        for title in [
+            "Die Zwerge (Band 6)",
+            "Der Totenarzt Teil 2",
+            "Feuer und Blut – Teil 1",
+            "Never - Die letzte Entscheidung",
+            "Krieg / Frieden",
+            "Krieg / Bd. 2",
+            "Sammelband 3",
+            "Bd. 7",
+        ] {
+            assert_eq!(series::parse_series(title), series_title(title, "", None));
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_series_progress() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let mut other = release("Knochenkälte", Some(7), 30);
+        other.author = "Simon Beckett".to_string();
+        other.series = "David Hunter".to_string();
+        let mut no_series = release("Miese kleine Morde", None, 1);
+        no_series.series = String::new();
+        let releases = vec![
+            release("Natrium Chlorid", Some(9), 5),
+            release("Verraten", Some(10), 20),
+            release("Erlöst", None, 25),
+            other,
+            no_series,
+        ];
+
+        // the series are sorted by author, the volumes by number
+        assert_eq!(
+            series::series_progress(&releases, Locale::En),
+            vec![
+                "Jussi Adler-Olsen - Sonderdezernat Q: Bd.9, Bd.10 and \"Erlöst\" upcoming",
+                "Simon Beckett - David Hunter: Bd.7 upcoming",
+            ]
+        );
+        assert!(series::series_progress(&releases[4..], Locale::En).is_empty());
+
+        // the summary is given in the language of the locale
+        assert_eq!(
+            series::series_progress(&releases[..3], Locale::De),
+            vec!["Jussi Adler-Olsen - Sonderdezernat Q: Bd.9, Bd.10 und \"Erlöst\" angekündigt"]
+        );
+        assert_eq!(
+            series::series_progress(&releases[..3], Locale::Fr),
+            vec!["Jussi Adler-Olsen - Sonderdezernat Q: Bd.9, Bd.10 et « Erlöst » à paraître"]
+        );
+
+        // the series is shown behind the title and its progress at the end of the list
+        let contents = releases::render_text(&releases[..2], Locale::De).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(
+            lines[4],
+            "Jussi Adler-Olsen - \"Natrium Chlorid\" (Sonderdezernat Q Bd.9)"
+        );
+        assert_eq!(
+            &lines[lines.len() - 3..],
+            [
+                "Reihen",
+                "-----------------------------------------------------------------------------------",
+                "Jussi Adler-Olsen - Sonderdezernat Q: Bd.9 und Bd.10 angekündigt",
+            ]
+        );
+        let contents = releases::render_text(&releases[..2], Locale::En).unwrap();
+        assert!(contents.contains("\nSeries\n"));
+    }
+}
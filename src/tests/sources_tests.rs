@@ -0,0 +1,414 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::SearchConfig;
+    use crate::customtypes::{Author, MediaFormat};
+    use crate::fetcher::{self, Fetcher};
+    use crate::logger;
+    use crate::sources::{self, TileSelectors};
+    use serial_test::serial;
+
+    const LOGLEVEL: &str = "Trace";
+    const AUTHOR: &str = "Beckett, Simon";
+    const FORMATTED_AUTHOR: &str = "Simon Beckett";
+    const TILE: &str = "div.tile";
+    const BASE_URL: &str = "https://www.weltbild.de";
+
+    fn today() -> chrono::NaiveDate {
+        chrono::NaiveDate::from_ymd_opt(2024, 9, 1).unwrap()
+    }
+    const HTML_CONTENT: &str = r#"
+        <html><body>
+            <div class="tile">
+                <a href="/artikel/buch/knochenkaelte_12345-1">Knochenkälte / David Hunter Bd.7</a>
+                <span>Simon Beckett</span>
+                <span>Buch (Gebunden)</span>
+                <span>Erscheint am 30.09.2024</span>
+            </div>
+            <div class="tile">
+                <span>Die Chemie des Todes</span>
+                <span>Simon Beckett</span>
+                <span>Hörbuch</span>
+                <span>Erscheint am 01.10.2024</span>
+            </div>
+            <div class="tile">
+                <span>Der Totenarzt</span>
+                <span>Chris Carter</span>
+                <span>Taschenbuch</span>
+                <span>Erscheint am 30.05.2024</span>
+            </div>
+        </body></html>"#;
+
+    const FIELDS: TileSelectors = TileSelectors {
+        title: "a.title",
+        author: "span.author",
+        format: "span.format",
+        price: "span.price",
+        publisher: "span.publisher",
+        date: "span.availability",
+    };
+    const STRUCTURED_HTML_CONTENT: &str = r#"
+        <html><body>
+            <div class="tile">
+                <span class="series">David Hunter Bd. 7</span>
+                <a class="title" href="/artikel/buch/knochenkaelte_12345-1">Knochenkälte
+                    Ein Fall für David Hunter</a>
+                <span class="subtitle">Thriller</span>
+                <span class="author">Simon Beckett</span>
+                <span class="format">Buch (Gebunden)</span>
+                <span class="price">26.00 €</span>
+                <span class="publisher">Wunderlich</span>
+                <span class="isbn">ISBN: 978-3-8052-0093-6</span>
+                <img data-src="/p/knochenkaelte_12345.jpg" src="/img/placeholder.gif">
+                <span class="availability">Erscheint am 30.09.2024</span>
+            </div>
+            <div class="tile">
+                <span>Die Chemie des Todes</span>
+                <span>Simon Beckett</span>
+                <span class="format">Taschenbuch</span>
+                <span>Verlag: rororo</span>
+                <span>Auch als Hörbuch erhältlich</span>
+                <span>Erscheint am 01.10.2024</span>
+            </div>
+            <div class="tile">
+                <a class="title" href="/artikel/buch/die-stimme_23456-1">Die Stimme</a>
+                <span class="author">Hanna Winter (Autor) und Simon Beckett / Lars Kepler</span>
+                <span class="format">Taschenbuch</span>
+                <span class="availability">Erscheint am 15.10.2024</span>
+            </div>
+            <div class="tile">
+                <a class="title" href="/artikel/buch/totenfang_34567-1">Totenfang</a>
+                <span class="author">Simon Becketts Erben</span>
+                <span class="format">Taschenbuch</span>
+                <span class="availability">Erscheint am 20.10.2024</span>
+            </div>
+        </body></html>"#;
+
+    const PAGES_DIR: &str = "/tmp/upcoming-releases-pages";
+    const NEXT_PAGE_URL: &str = "https://www.weltbild.de/suche/Beckett+Simon?seite=2";
+
+    /// A Weltbild search result page with a tile per title, released on the given day of October.
+    fn weltbild_page(titles: &[(&str, u32)], next_page: Option<&str>) -> String {
+        let tiles: String = titles
+            .iter()
+            .map(|(title, day)| {
+                format!(
+                    r#"<div class="inner-flex-container">
+                        <a class="product-title" href="/artikel/buch/{title}">{title}</a>
+                        <div class="product-author">Simon Beckett</div>
+                        <div class="product-format">Taschenbuch</div>
+                        <div class="product-availability">Erscheint am {day:02}.10.2024</div>
+                    </div>"#
+                )
+            })
+            .collect();
+        let next_link = next_page
+            .map(|url| format!(r#"<a rel="next" href="{}">Weiter</a>"#, url))
+            .unwrap_or_default();
+
+        format!("<html><body>{}{}</body></html>", tiles, next_link)
+    }
+
+    #[test]
+    #[serial]
+    fn test_create_sources() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let names = vec![
+            "Weltbild".to_string(),
+            "thalia".to_string(),
+            "HUGENDUBEL".to_string(),
+        ];
+        let release_sources =
+            sources::create_sources(&names, &SearchConfig::default(), today()).unwrap();
+
+        let source_names: Vec<&str> = release_sources.iter().map(|s| s.name()).collect();
+        assert_eq!(source_names, vec!["Weltbild", "Thalia", "Hugendubel"]);
+    }
+
+    #[test]
+    #[serial]
+    fn test_weltbild_search_url() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let author = Author::create(AUTHOR.to_string());
+
+        // the default search parameters
+        let weltbild =
+            sources::create_sources(&["weltbild".to_string()], &SearchConfig::default(), today())
+                .unwrap();
+        let languages = weltbild[0].languages(&author);
+        assert_eq!(languages, vec!["/language/ger".to_string()]);
+        assert_eq!(
+            weltbild[0].search_url(&author, &languages[0]).unwrap(),
+            "https://www.weltbild.de/suche/Beckett+Simon?jahr=0&node=%2Fbuecher&sprache=%2Flanguage%2Fger"
+        );
+
+        // configured search parameters, without languages all languages are searched at once
+        let search = SearchConfig {
+            release_year: "2025".to_string(),
+            media_type: Some("/ebooks".to_string()),
+            languages: Vec::new(),
+            ..SearchConfig::default()
+        };
+        let weltbild =
+            sources::create_sources(&["weltbild".to_string()], &search, today()).unwrap();
+        assert_eq!(weltbild[0].languages(&author), vec![String::new()]);
+        assert_eq!(
+            weltbild[0].search_url(&author, "").unwrap(),
+            "https://www.weltbild.de/suche/Beckett+Simon?jahr=2025&node=%2Febooks"
+        );
+
+        // the languages of the authors list override the configured ones
+        let mut author = author;
+        author.languages = Some(vec![
+            "/language/eng".to_string(),
+            "/language/ger".to_string(),
+        ]);
+        assert_eq!(weltbild[0].languages(&author).len(), 2);
+        assert_eq!(
+            weltbild[0].search_url(&author, "/language/eng").unwrap(),
+            "https://www.weltbild.de/suche/Beckett+Simon?jahr=2025&node=%2Febooks&sprache=%2Flanguage%2Feng"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_parse_tiles() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let formats = SearchConfig::default().formats;
+
+        // only the book of the author is accepted, no audio book and no other author
+        let releases = sources::parse_tiles(
+            HTML_CONTENT,
+            TILE,
+            None,
+            BASE_URL,
+            AUTHOR,
+            &formats,
+            today(),
+        );
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].author, FORMATTED_AUTHOR);
+        // the series and its volume are split off the title
+        assert_eq!(releases[0].title, "Knochenkälte");
+        assert_eq!(releases[0].series, "David Hunter");
+        assert_eq!(releases[0].volume, Some(7));
+        assert_eq!(releases[0].format, "Hardcover");
+        assert_eq!(
+            releases[0].url,
+            "https://www.weltbild.de/artikel/buch/knochenkaelte_12345-1"
+        );
+
+        // releases before today are no upcoming releases
+        let later = chrono::NaiveDate::from_ymd_opt(2024, 10, 1).unwrap();
+        assert!(
+            sources::parse_tiles(HTML_CONTENT, TILE, None, BASE_URL, AUTHOR, &formats, later)
+                .is_empty()
+        );
+
+        // only the configured formats are accepted
+        let audio_books = vec![MediaFormat::Hoerbuch];
+        let releases = sources::parse_tiles(
+            HTML_CONTENT,
+            TILE,
+            None,
+            BASE_URL,
+            AUTHOR,
+            &audio_books,
+            today(),
+        );
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].title, "Die Chemie des Todes");
+        assert_eq!(releases[0].format, "Hörbuch");
+
+        // the format is taken from its own line, not from a blurb mentioning other formats
+        let blurb = r#"
+            <html><body>
+                <div class="tile">
+                    <span>Totenfang</span>
+                    <span>Simon Beckett</span>
+                    <span>Der fünfte Fall für David Hunter. Auch als Hörbuch und eBook erhältlich.</span>
+                    <span>Taschenbuch</span>
+                    <span>Erscheint am 20.10.2024</span>
+                </div>
+            </body></html>"#;
+        let releases = sources::parse_tiles(blurb, TILE, None, BASE_URL, AUTHOR, &formats, today());
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].format, "Taschenbuch");
+        assert!(
+            sources::parse_tiles(blurb, TILE, None, BASE_URL, AUTHOR, &audio_books, today())
+                .is_empty()
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_parse_tiles_with_selectors() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let formats = SearchConfig::default().formats;
+        let releases = sources::parse_tiles(
+            STRUCTURED_HTML_CONTENT,
+            TILE,
+            Some(&FIELDS),
+            BASE_URL,
+            AUTHOR,
+            &formats,
+            today(),
+        );
+        assert_eq!(releases.len(), 3);
+
+        // the wrapped title is taken from its own element, not from the line before the author
+        assert_eq!(releases[0].title, "Knochenkälte Ein Fall für David Hunter");
+        assert_eq!(releases[0].format, "Hardcover");
+        assert_eq!(releases[0].price, "26.00 €");
+        assert_eq!(releases[0].publisher, "Wunderlich");
+        assert_eq!(releases[0].isbn, "9783805200936");
+        assert_eq!(
+            releases[0].url,
+            "https://www.weltbild.de/artikel/buch/knochenkaelte_12345-1"
+        );
+        assert_eq!(
+            releases[0].cover_url,
+            "https://www.weltbild.de/p/knochenkaelte_12345.jpg"
+        );
+        assert_eq!(
+            releases[0].date.format("%Y-%m-%d").to_string(),
+            "2024-09-30"
+        );
+
+        // the tile without the dedicated title falls back to the text lines, but the format is
+        // still taken from its own element
+        assert_eq!(releases[1].title, "Die Chemie des Todes");
+        assert_eq!(releases[1].format, "Taschenbuch");
+        assert_eq!(releases[1].price, "");
+        assert_eq!(releases[1].publisher, "rororo");
+        assert_eq!(releases[1].isbn, "");
+        assert_eq!(releases[1].cover_url, "");
+        assert_eq!(releases[1].contributors, vec![FORMATTED_AUTHOR.to_string()]);
+
+        // the author is matched anywhere in the list of contributors, but not as part of a name
+        assert_eq!(releases[2].title, "Die Stimme");
+        assert_eq!(releases[2].author, FORMATTED_AUTHOR);
+        assert_eq!(
+            releases[2].contributors,
+            vec!["Hanna Winter", "Simon Beckett", "Lars Kepler"]
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_search_pages() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let author = Author::create(AUTHOR.to_string());
+        let weltbild =
+            sources::create_sources(&["weltbild".to_string()], &SearchConfig::default(), today())
+                .unwrap();
+        let first_url = weltbild[0].search_url(&author, "/language/ger").unwrap();
+
+        // the first page links to the second page, which has no further page
+        std::fs::create_dir_all(PAGES_DIR).unwrap();
+        std::fs::write(
+            format!("{}/{}", PAGES_DIR, fetcher::offline_file_name(&first_url)),
+            weltbild_page(
+                &[("Totenfang", 1), ("Knochenkälte", 2)],
+                Some("/suche/Beckett+Simon?seite=2"),
+            ),
+        )
+        .unwrap();
+        std::fs::write(
+            format!(
+                "{}/{}",
+                PAGES_DIR,
+                fetcher::offline_file_name(NEXT_PAGE_URL)
+            ),
+            weltbild_page(&[("Die Verlorenen", 3)], None),
+        )
+        .unwrap();
+        let fetcher = Fetcher::Offline(PAGES_DIR.into());
+
+        let search_titles = |search: SearchConfig| {
+            let fetcher = &fetcher;
+            let author = &author;
+            async move {
+                let weltbild =
+                    sources::create_sources(&["weltbild".to_string()], &search, today()).unwrap();
+                weltbild[0]
+                    .search(fetcher, author)
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .map(|release| {
+                        assert_eq!(release.language, "ger");
+                        release.title
+                    })
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        // the pages are followed until there is no further page
+        assert_eq!(
+            search_titles(SearchConfig::default()).await,
+            vec!["Totenfang", "Knochenkälte", "Die Verlorenen"]
+        );
+
+        // the maximum number of pages is respected
+        let search = SearchConfig {
+            max_pages: 1,
+            ..SearchConfig::default()
+        };
+        assert_eq!(
+            search_titles(search).await,
+            vec!["Totenfang", "Knochenkälte"]
+        );
+
+        // more releases than the maximum number of results are dropped
+        let search = SearchConfig {
+            max_results: 2,
+            ..SearchConfig::default()
+        };
+        assert_eq!(
+            search_titles(search).await,
+            vec!["Totenfang", "Knochenkälte"]
+        );
+
+        // cleanup
+        let _ = std::fs::remove_dir_all(PAGES_DIR);
+    }
+
+    #[test]
+    #[serial]
+    fn test_sources_error_cases() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let search = SearchConfig::default();
+
+        // test unknown and missing release sources
+        assert!(sources::create_sources(&["amazon".to_string()], &search, today()).is_err());
+        assert!(sources::create_sources(&[], &search, today()).is_err());
+
+        // test author name in wrong format and invalid selector
+        assert!(sources::parse_tiles(
+            HTML_CONTENT,
+            TILE,
+            None,
+            BASE_URL,
+            "",
+            &search.formats,
+            today()
+        )
+        .is_empty());
+        assert!(sources::parse_tiles(
+            HTML_CONTENT,
+            "<>",
+            None,
+            BASE_URL,
+            AUTHOR,
+            &search.formats,
+            today()
+        )
+        .is_empty());
+    }
+}
@@ -0,0 +1,202 @@
+#[cfg(test)]
+mod tests {
+    use crate::customtypes::{ChangeKind, UpcomingRelease};
+    use crate::logger;
+    use crate::state;
+    use serial_test::serial;
+
+    const LOGLEVEL: &str = "Trace";
+    const STATE_FILE: &str = "/tmp/releases-state.json";
+
+    fn today() -> chrono::NaiveDate {
+        chrono::NaiveDate::from_ymd_opt(2024, 9, 1).unwrap()
+    }
+
+    fn release(author: &str, title: &str, day: u32, url: &str) -> UpcomingRelease {
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 9, day)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc();
+
+        let mut release = UpcomingRelease::create(author.to_string(), title.to_string(), date);
+        release.source = "Weltbild".to_string();
+        release.url = url.to_string();
+        release
+    }
+
+    #[test]
+    #[serial]
+    fn test_save_and_load_state() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let _ = std::fs::remove_file(STATE_FILE);
+
+        // no state file means no previous run
+        assert!(state::load_state(STATE_FILE).unwrap().is_empty());
+
+        let releases = vec![
+            release("Simon Beckett", "Knochenkälte", 30, "https://weltbild.de/1"),
+            release("Marc Elsberg", "°C - Celsius", 9, ""),
+        ];
+        state::save_state(STATE_FILE, &releases).unwrap();
+
+        let loaded = state::load_state(STATE_FILE).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].title, "Knochenkälte");
+        assert_eq!(loaded[0].date, releases[0].date);
+        assert_eq!(loaded[0].url, releases[0].url);
+        assert_eq!(loaded[1].author, "Marc Elsberg");
+
+        // a state file of schema version 1 is migrated
+        std::fs::write(
+            STATE_FILE,
+            r#"{"schema_version": 1, "releases": [{
+                "author": "Simon Beckett",
+                "title": "Knochenkälte / David Hunter Bd.7",
+                "contributors": ["Simon Beckett"],
+                "date": "2024-09-30",
+                "source": "Weltbild",
+                "url": "https://weltbild.de/1",
+                "format": "Buch"
+            }]}"#,
+        )
+        .unwrap();
+        let loaded = state::load_state(STATE_FILE).unwrap();
+        assert_eq!(loaded[0].title, "Knochenkälte");
+        assert_eq!(loaded[0].series, "David Hunter");
+        assert_eq!(loaded[0].volume, Some(7));
+        assert_eq!(loaded[0].format, "Hardcover");
+
+        // cleanup
+        let _ = std::fs::remove_file(STATE_FILE);
+        assert!(!std::path::Path::new(STATE_FILE).exists());
+    }
+
+    #[test]
+    #[serial]
+    fn test_compare() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let previous = vec![
+            release("Simon Beckett", "Knochenkälte", 30, "https://weltbild.de/1"),
+            release("Marc Elsberg", "°C - Celsius", 9, ""),
+            release("Chris Carter", "Der Totenarzt", 1, "https://weltbild.de/2"),
+            release("Ken Follett", "Never", 26, "https://weltbild.de/3"),
+            release("Dan Brown", "Sakrileg", 2, "https://weltbild.de/4"),
+        ];
+        let current = vec![
+            release("Simon Beckett", "Knochenkälte", 30, "https://weltbild.de/1"),
+            release("Marc Elsberg", "°C  -  CELSIUS", 9, ""),
+            release("Chris Carter", "Der Totenarzt", 15, "https://weltbild.de/2"),
+            release(
+                "Ken Follett",
+                "Never - Die letzte Entscheidung",
+                26,
+                "https://weltbild.de/3",
+            ),
+            release("Andreas Eschbach", "Der schlauste Mann der Welt", 30, ""),
+        ];
+
+        let changes = state::compare(&previous, &current, &[], today());
+        let kinds: Vec<(&str, &ChangeKind)> = changes
+            .iter()
+            .map(|change| (change.release.author.as_str(), &change.kind))
+            .collect();
+
+        // unchanged releases are not reported, the titles are compared normalized
+        assert_eq!(kinds.len(), 5);
+        assert_eq!(
+            kinds[0],
+            (
+                "Marc Elsberg",
+                &ChangeKind::TitleChanged("°C - Celsius".to_string())
+            )
+        );
+        assert_eq!(
+            kinds[1],
+            ("Chris Carter", &ChangeKind::DateChanged(previous[2].date))
+        );
+        assert_eq!(
+            kinds[2],
+            (
+                "Ken Follett",
+                &ChangeKind::TitleChanged("Never".to_string())
+            )
+        );
+        assert_eq!(kinds[3], ("Andreas Eschbach", &ChangeKind::New));
+        assert_eq!(kinds[4], ("Dan Brown", &ChangeKind::Disappeared));
+
+        // releases of unchecked authors did not disappear and are kept in the state
+        let unchecked_authors = vec!["Dan Brown".to_string()];
+        let changes = state::compare(&previous, &current, &unchecked_authors, today());
+        assert!(changes
+            .iter()
+            .all(|change| change.kind != ChangeKind::Disappeared));
+
+        let next_state = state::next_state(&previous, &current, &unchecked_authors, today());
+        assert_eq!(next_state.len(), 6);
+        assert_eq!(next_state[5].title, "Sakrileg");
+
+        // releases found anyway, e.g. via a pseudonym of the unchecked author, are not doubled
+        let next_state = state::next_state(&next_state, &current, &unchecked_authors, today());
+        assert_eq!(next_state.len(), 6);
+        let next_state = state::next_state(&previous, &next_state, &unchecked_authors, today());
+        assert_eq!(next_state.len(), 6);
+
+        // released books are not found as upcoming releases anymore, they did not disappear
+        let after_release = chrono::NaiveDate::from_ymd_opt(2024, 9, 3).unwrap();
+        let changes = state::compare(&previous, &current, &[], after_release);
+        assert_eq!(changes.len(), 4);
+        assert!(changes
+            .iter()
+            .all(|change| change.kind != ChangeKind::Disappeared));
+
+        // and they are not kept for unchecked authors either
+        let next_state = state::next_state(&previous, &current, &unchecked_authors, after_release);
+        assert_eq!(next_state.len(), 5);
+        assert!(next_state.iter().all(|release| release.title != "Sakrileg"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_render_report() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        let previous = vec![release("Chris Carter", "Der Totenarzt", 1, "")];
+        let current = vec![
+            release("Chris Carter", "Der Totenarzt", 15, ""),
+            release("Simon Beckett", "Knochenkälte", 30, ""),
+        ];
+
+        let report =
+            state::render_report(&state::compare(&previous, &current, &[], today())).unwrap();
+        assert_eq!(
+            report,
+            "Changes since last run\n\n\
+             [date changed] Chris Carter - \"Der Totenarzt\" (2024-09-01 -> 2024-09-15)\n\
+             [new] Simon Beckett - \"Knochenkälte\" (2024-09-30)\n"
+        );
+
+        let report = state::render_report(&[]).unwrap();
+        assert!(report.ends_with("No new or changed releases\n"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_state_error_cases() {
+        logger::init_logger(LOGLEVEL).expect("Could not initialize logger");
+
+        // test state file with invalid contents
+        std::fs::write(STATE_FILE, "no json").unwrap();
+        assert!(state::load_state(STATE_FILE).is_err());
+
+        // test state file with another schema version
+        std::fs::write(STATE_FILE, r#"{"schema_version": 0, "releases": []}"#).unwrap();
+        assert!(state::load_state(STATE_FILE).is_err());
+
+        // cleanup
+        let _ = std::fs::remove_file(STATE_FILE);
+        assert!(!std::path::Path::new(STATE_FILE).exists());
+    }
+}